@@ -0,0 +1,38 @@
+//! Throughput of formatting numbers whose digits dominate the cost.
+//!
+//! Run with `cargo bench --bench digits`.
+
+use num_runtime_fmt::{Dynamic, NumFmt, Numeric};
+use std::{hint::black_box, time::Instant};
+
+const ITERATIONS: u32 = 200_000;
+
+fn bench<N: Numeric + Copy>(name: &str, spec: &str, numbers: &[N]) {
+    let fmt = NumFmt::from_str(spec).unwrap();
+    let plan = fmt.compile::<N>(Dynamic::default()).unwrap();
+    let mut buffer = String::with_capacity(256);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for &number in numbers {
+            buffer.clear();
+            plan.write_to(&mut buffer, black_box(number)).unwrap();
+            black_box(&buffer);
+        }
+    }
+    let per_number = start.elapsed() / (ITERATIONS * numbers.len() as u32);
+    println!("{:<12} {:>8} {:>10?}/number", name, spec, per_number);
+}
+
+fn main() {
+    let u64s = [0, 7, 1_234, 987_654_321, u64::MAX];
+    let u128s = [0, 42, u64::MAX as u128 + 1, u128::MAX];
+    let f64s = [0.0, 1.5, 1234.5678, 0.000123, 6.02214076e23];
+
+    bench("u64", "", &u64s);
+    bench("u64", ",", &u64s);
+    bench("u64", "x", &u64s);
+    bench("u128", "", &u128s);
+    bench("u128", "x", &u128s);
+    bench("f64", "", &f64s);
+    bench("f64", ".3", &f64s);
+}
@@ -5,12 +5,15 @@
 /// - `Left`: the output is left-aligned in `width` columns.
 /// - `Decimal`: `width` sets the minimal width before the decimal. For integers,
 ///   equivalent to `Right`.
+/// - `AfterSign`: the output is right-aligned in `width` columns, but the padding is placed
+///   between the sign and base specification and the digits, i.e. `-    1`.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Align {
     Left,
     Center,
     Right,
     Decimal,
+    AfterSign,
 }
 
 impl Default for Align {
@@ -0,0 +1,107 @@
+use crate::{Part, PartKind};
+use alloc::{collections::BTreeMap, string::String};
+
+/// ANSI terminal styling of the parts of a formatted number. See [`NumFmt::fmt_ansi`].
+///
+/// Styles are given as SGR parameters, i.e. `"31"` for red or `"1;4"` for bold and underlined.
+/// Each [kind of part][PartKind] can be styled independently, and the digits and sign of
+/// negative numbers can additionally be styled. Nothing is styled by default.
+///
+/// Styling never affects the `width` of the number: padding is computed on the visible
+/// characters only.
+///
+/// ```rust
+/// # use num_runtime_fmt::{AnsiStyle, NumFmt, PartKind};
+/// let style = AnsiStyle::new()
+///     .negative("31")
+///     .part(PartKind::GroupSeparator, "2");
+/// let fmt = NumFmt::from_str(">7,").unwrap();
+/// assert_eq!(
+///     fmt.fmt_ansi(-1234, &style).unwrap(),
+///     " \x1b[31m-\x1b[0m\x1b[31m1\x1b[0m\x1b[31;2m,\x1b[0m\x1b[31m234\x1b[0m",
+/// );
+/// ```
+///
+/// [`NumFmt::fmt_ansi`]: crate::NumFmt::fmt_ansi
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct AnsiStyle {
+    parts: BTreeMap<PartKind, String>,
+    negative: Option<String>,
+}
+
+impl AnsiStyle {
+    /// Construct an `AnsiStyle` which styles nothing.
+    pub fn new() -> AnsiStyle {
+        AnsiStyle::default()
+    }
+
+    /// A palette suited to tables: negative numbers are red, padding and group separators are
+    /// dim, and exponents and units are cyan.
+    pub fn table() -> AnsiStyle {
+        AnsiStyle::new()
+            .negative("31")
+            .part(PartKind::Padding, "2")
+            .part(PartKind::GroupSeparator, "2")
+            .part(PartKind::Exponent, "36")
+            .part(PartKind::Prefix, "36")
+            .part(PartKind::Suffix, "36")
+            .part(PartKind::Currency, "36")
+    }
+
+    /// Style every part of this kind with the given SGR parameters.
+    pub fn part(mut self, kind: PartKind, sgr: impl Into<String>) -> Self {
+        self.parts.insert(kind, sgr.into());
+        self
+    }
+
+    /// Style the sign, digits, separators and exponent of negative numbers with the given SGR
+    /// parameters, in addition to the style of each part.
+    pub fn negative(mut self, sgr: impl Into<String>) -> Self {
+        self.negative = Some(sgr.into());
+        self
+    }
+
+    /// SGR parameters of the style of this kind of part, if any.
+    pub fn style_of(&self, kind: PartKind) -> Option<&str> {
+        self.parts.get(&kind).map(String::as_str)
+    }
+
+    /// SGR parameters of the style of negative numbers, if any.
+    pub fn negative_style(&self) -> Option<&str> {
+        self.negative.as_deref()
+    }
+
+    /// Push a part to `out`, wrapped in escape codes if it is styled.
+    pub(crate) fn paint(&self, part: &Part, negative: bool, out: &mut String) {
+        let negative = self.negative_style().filter(|_| {
+            negative
+                && matches!(
+                    part.kind,
+                    PartKind::Sign
+                        | PartKind::Integer
+                        | PartKind::GroupSeparator
+                        | PartKind::DecimalSeparator
+                        | PartKind::Fraction
+                        | PartKind::Exponent
+                )
+        });
+        let styles = [negative, self.style_of(part.kind)];
+        let mut styles = styles.iter().flatten();
+        let first = match styles.next() {
+            Some(first) => first,
+            None => {
+                out.push_str(&part.text);
+                return;
+            }
+        };
+        out.push_str("\x1b[");
+        out.push_str(first);
+        for style in styles {
+            out.push(';');
+            out.push_str(style);
+        }
+        out.push('m');
+        out.push_str(&part.text);
+        out.push_str("\x1b[0m");
+    }
+}
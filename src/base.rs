@@ -1,3 +1,6 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
 /// The base / format with which to represent this number.
 ///
 /// - `Binary`: Emit this number's binary representation
@@ -20,3 +23,76 @@ impl Default for Base {
         Self::Decimal
     }
 }
+
+/// The style of base specification printed with the number when `#` is set.
+///
+/// - `Rust`: `0b`, `0o`, `0d` and `0x` prefixes (default)
+/// - `C`: `0b` and `0x` prefixes, `0X` for uppercase hex, and a `0` prefix for octal.
+///   Decimal numbers are unmarked.
+/// - `Intel`: `b`, `o`, `d` and `h` suffixes, i.e. `1Fh`
+/// - `Motorola`: `%`, `@` and `$` prefixes, i.e. `$1F`. Decimal numbers are unmarked.
+/// - `VisualBasic`: `&B`, `&O` and `&H` prefixes, i.e. `&H1F`. Decimal numbers are unmarked.
+/// - `Custom`: the provided prefix and suffix, whatever the base. This is useful for i.e. CSS
+///   colors, which are hex numbers prefixed with `#`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BaseMarker {
+    Rust,
+    C,
+    Intel,
+    Motorola,
+    VisualBasic,
+    #[cfg(feature = "alloc")]
+    Custom {
+        prefix: String,
+        suffix: String,
+    },
+}
+
+impl Default for BaseMarker {
+    #[inline]
+    fn default() -> Self {
+        Self::Rust
+    }
+}
+
+impl BaseMarker {
+    /// The prefix and suffix marking a number in this base.
+    pub fn affixes(&self, base: Base) -> (&str, &str) {
+        match (self, self.static_affixes(base)) {
+            (_, Some(affixes)) => affixes,
+            #[cfg(feature = "alloc")]
+            (Self::Custom { prefix, suffix }, None) => (prefix, suffix),
+            _ => unreachable!("only custom affixes are not static"),
+        }
+    }
+
+    /// The prefix and suffix of every style but `Custom`, which are `'static`.
+    pub(crate) fn static_affixes(&self, base: Base) -> Option<(&'static str, &'static str)> {
+        use Base::*;
+        Some(match (self, base) {
+            (Self::Rust, Binary) => ("0b", ""),
+            (Self::Rust, Octal) => ("0o", ""),
+            (Self::Rust, Decimal) => ("0d", ""),
+            (Self::Rust, LowerHex) | (Self::Rust, UpperHex) => ("0x", ""),
+            (Self::C, Binary) => ("0b", ""),
+            (Self::C, Octal) => ("0", ""),
+            (Self::C, Decimal) => ("", ""),
+            (Self::C, LowerHex) => ("0x", ""),
+            (Self::C, UpperHex) => ("0X", ""),
+            (Self::Intel, Binary) => ("", "b"),
+            (Self::Intel, Octal) => ("", "o"),
+            (Self::Intel, Decimal) => ("", "d"),
+            (Self::Intel, LowerHex) | (Self::Intel, UpperHex) => ("", "h"),
+            (Self::Motorola, Binary) => ("%", ""),
+            (Self::Motorola, Octal) => ("@", ""),
+            (Self::Motorola, Decimal) => ("", ""),
+            (Self::Motorola, LowerHex) | (Self::Motorola, UpperHex) => ("$", ""),
+            (Self::VisualBasic, Binary) => ("&B", ""),
+            (Self::VisualBasic, Octal) => ("&O", ""),
+            (Self::VisualBasic, Decimal) => ("", ""),
+            (Self::VisualBasic, LowerHex) | (Self::VisualBasic, UpperHex) => ("&H", ""),
+            #[cfg(feature = "alloc")]
+            (Self::Custom { .. }, _) => return None,
+        })
+    }
+}
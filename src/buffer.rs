@@ -0,0 +1,319 @@
+use crate::Error;
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Capacity of the bytes a [`Buffer`] stores on the stack.
+///
+/// Zeros at either end of the digits are not stored, so this is enough for the digits of any
+/// primitive number.
+pub(crate) const CAPACITY: usize = 128;
+
+/// Marker of a group separator among the digits of a [`Grouped`].
+pub(crate) const SEPARATOR: u8 = 0xff;
+
+/// Growable buffer of ASCII digits.
+///
+/// Runs of zeros at either end, such as the zeros padding a number to a width, or those between
+/// the decimal and the first significant digit of a small number, are counted rather than
+/// stored. The remaining bytes are stored on the stack, up to [`CAPACITY`] of them. With the
+/// `alloc` feature, further bytes spill to the heap; otherwise pushing them fails with
+/// [`Error::Capacity`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Buffer {
+    /// Zeros preceding the stored bytes.
+    leading: usize,
+    bytes: Bytes,
+    /// Zeros following the stored bytes; only counted once a byte is stored.
+    trailing: usize,
+}
+
+impl Buffer {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect the digits of an iterator. Digits must be ASCII.
+    pub(crate) fn from_digits(digits: impl Iterator<Item = char>) -> Result<Self, Error> {
+        let mut buffer = Buffer::new();
+        for digit in digits {
+            debug_assert!(digit.is_ascii(), "illegal digit {:?}", digit);
+            buffer.push(digit as u8)?;
+        }
+        Ok(buffer)
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.leading + self.bytes.len() + self.trailing
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn push(&mut self, byte: u8) -> Result<(), Error> {
+        if byte == b'0' {
+            if self.bytes.is_empty() {
+                self.leading += 1;
+            } else {
+                self.trailing += 1;
+            }
+            return Ok(());
+        }
+        while self.trailing > 0 {
+            self.store(b'0')?;
+            self.trailing -= 1;
+        }
+        self.store(byte)
+    }
+
+    #[inline]
+    fn store(&mut self, byte: u8) -> Result<(), Error> {
+        self.bytes.push(byte)
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<u8> {
+        if self.trailing > 0 {
+            self.trailing -= 1;
+            return Some(b'0');
+        }
+        if let Some(byte) = self.bytes.pop() {
+            return Some(byte);
+        }
+        self.leading = self.leading.checked_sub(1)?;
+        Some(b'0')
+    }
+
+    pub(crate) fn last(&self) -> Option<u8> {
+        if self.trailing > 0 {
+            return Some(b'0');
+        }
+        match self.bytes.last() {
+            Some(byte) => Some(byte),
+            None if self.leading > 0 => Some(b'0'),
+            None => None,
+        }
+    }
+
+    /// The digit at `idx`, which must be less than the length.
+    #[inline]
+    fn get(&self, idx: usize) -> u8 {
+        idx.checked_sub(self.leading)
+            .and_then(|idx| self.bytes.get(idx))
+            .unwrap_or(b'0')
+    }
+
+    pub(crate) fn iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = u8> + ExactSizeIterator + Clone + '_ {
+        (0..self.len()).map(move |idx| self.get(idx))
+    }
+
+    /// Append zeros until the buffer is `len` digits long.
+    pub(crate) fn pad_zeros(&mut self, len: usize) {
+        let missing = len.saturating_sub(self.len());
+        if self.bytes.is_empty() {
+            self.leading += missing;
+        } else {
+            self.trailing += missing;
+        }
+    }
+}
+
+/// Integer digits, least significant first, with a group separator between each group of
+/// `spacing` digits.
+///
+/// The separators are generated on the fly rather than stored, and are represented by
+/// [`SEPARATOR`].
+#[derive(Clone, Debug)]
+pub(crate) struct Grouped {
+    digits: Buffer,
+    spacing: Option<usize>,
+    /// Whether a separator follows the most significant digit, once the digit above it is popped.
+    separator_on_top: bool,
+}
+
+impl Grouped {
+    /// Group `digits` by `spacing`, which must not be 0.
+    pub(crate) fn new(digits: Buffer, spacing: Option<usize>) -> Self {
+        debug_assert_ne!(spacing, Some(0), "spacing must not be 0");
+        Grouped {
+            digits,
+            spacing,
+            separator_on_top: false,
+        }
+    }
+
+    /// Count of the separators between the digits.
+    fn separators(&self) -> usize {
+        match self.spacing {
+            Some(spacing) => self.digits.len().saturating_sub(1) / spacing,
+            None => 0,
+        }
+    }
+
+    /// Count of the digits and separators.
+    pub(crate) fn len(&self) -> usize {
+        self.digits.len() + self.separators() + self.separator_on_top as usize
+    }
+
+    pub(crate) fn last(&self) -> Option<u8> {
+        if self.separator_on_top {
+            return Some(SEPARATOR);
+        }
+        self.digits.last()
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<u8> {
+        if self.separator_on_top {
+            self.separator_on_top = false;
+            return Some(SEPARATOR);
+        }
+        let digit = self.digits.pop()?;
+        let remaining = self.digits.len();
+        self.separator_on_top = remaining > 0
+            && self
+                .spacing
+                .map(|spacing| remaining % spacing == 0)
+                .unwrap_or_default();
+        Some(digit)
+    }
+
+    /// Iterate over the digits and separators.
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = u8> + Clone + '_ {
+        (0..self.len()).map(move |idx| match self.spacing {
+            Some(spacing) if idx % (spacing + 1) == spacing => SEPARATOR,
+            Some(spacing) => self.digits.get(idx - idx / (spacing + 1)),
+            None => self.digits.get(idx),
+        })
+    }
+}
+
+/// Storage of the bytes of a [`Buffer`].
+///
+/// The first [`CAPACITY`] bytes are stored inline. With the `alloc` feature, the rest spill to
+/// a `Vec`, which only allocates once it is used.
+#[derive(Clone, Debug)]
+struct Bytes {
+    inline: [u8; CAPACITY],
+    inline_len: usize,
+    #[cfg(feature = "alloc")]
+    spilled: Vec<u8>,
+}
+
+impl Default for Bytes {
+    fn default() -> Self {
+        Bytes {
+            inline: [0; CAPACITY],
+            inline_len: 0,
+            #[cfg(feature = "alloc")]
+            spilled: Vec::new(),
+        }
+    }
+}
+
+impl Bytes {
+    #[inline]
+    fn len(&self) -> usize {
+        #[cfg(feature = "alloc")]
+        return self.inline_len + self.spilled.len();
+        #[cfg(not(feature = "alloc"))]
+        return self.inline_len;
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.inline_len == 0
+    }
+
+    #[inline]
+    fn push(&mut self, byte: u8) -> Result<(), Error> {
+        match self.inline.get_mut(self.inline_len) {
+            Some(slot) => {
+                *slot = byte;
+                self.inline_len += 1;
+            }
+            #[cfg(feature = "alloc")]
+            None => self.spilled.push(byte),
+            #[cfg(not(feature = "alloc"))]
+            None => return Err(Error::Capacity),
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<u8> {
+        #[cfg(feature = "alloc")]
+        if let Some(byte) = self.spilled.pop() {
+            return Some(byte);
+        }
+        self.inline_len = self.inline_len.checked_sub(1)?;
+        Some(self.inline[self.inline_len])
+    }
+
+    #[inline]
+    fn last(&self) -> Option<u8> {
+        match self.len() {
+            0 => None,
+            len => self.get(len - 1),
+        }
+    }
+
+    #[inline]
+    fn get(&self, idx: usize) -> Option<u8> {
+        if idx < self.inline_len {
+            return Some(self.inline[idx]);
+        }
+        #[cfg(feature = "alloc")]
+        return self.spilled.get(idx - self.inline_len).copied();
+        #[cfg(not(feature = "alloc"))]
+        return None;
+    }
+}
+
+/// Fixed-capacity string, for the output of the standard formatting machinery.
+///
+/// Writing beyond the capacity fails with a [`fmt::Error`].
+#[derive(Clone, Copy)]
+pub(crate) struct StackStr<const CAP: usize> {
+    bytes: [u8; CAP],
+    len: usize,
+}
+
+impl<const CAP: usize> Default for StackStr<CAP> {
+    fn default() -> Self {
+        StackStr {
+            bytes: [0; CAP],
+            len: 0,
+        }
+    }
+}
+
+impl<const CAP: usize> StackStr<CAP> {
+    pub(crate) fn as_str(&self) -> &str {
+        // only whole `str`s are ever written
+        core::str::from_utf8(&self.bytes[..self.len]).expect("contents are valid UTF-8")
+    }
+}
+
+impl<const CAP: usize> fmt::Write for StackStr<CAP> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl<const CAP: usize> fmt::Debug for StackStr<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
@@ -1,19 +1,45 @@
-use super::{Align, Base, NumFmt, Sign};
+use super::{
+    text::Text, Align, Base, BaseMarker, Currency, CurrencyDisplay, ExponentStyle, Notation,
+    NumFmt, Overflow, Ratio, Sign, WidthUnit, ZeroSign,
+};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 
 /// Builder for a numeric formatter.
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 pub struct Builder {
-    fill: Option<char>,
+    fill: Option<Text>,
+    right_fill: Option<Text>,
     align: Align,
     sign: Sign,
+    zero_sign: ZeroSign,
+    minus_sign: Option<char>,
+    plus_sign: Option<char>,
     hash: bool,
+    base_marker: BaseMarker,
     zero: bool,
     width: usize,
+    width_unit: WidthUnit,
+    max_width: Option<usize>,
+    overflow: Overflow,
+    min_integer_digits: Option<usize>,
+    max_integer_digits: Option<usize>,
     precision: Option<usize>,
+    min_fraction_digits: Option<usize>,
+    max_fraction_digits: Option<usize>,
+    force_decimal_point: bool,
     format: Base,
+    notation: Notation,
+    exponent_style: ExponentStyle,
     separator: Option<char>,
     spacing: Option<usize>,
     decimal_separator: Option<char>,
+    ratio: Option<Ratio>,
+    scale: i32,
+    currency: Option<Currency>,
+    currency_display: CurrencyDisplay,
+    prefix: Option<Text>,
+    suffix: Option<Text>,
 }
 
 impl Builder {
@@ -26,29 +52,76 @@ impl Builder {
     pub fn build(self) -> NumFmt {
         let Builder {
             fill,
+            right_fill,
             align,
             sign,
+            zero_sign,
+            minus_sign,
+            plus_sign,
             hash,
+            base_marker,
             zero,
             width,
+            width_unit,
+            max_width,
+            overflow,
+            min_integer_digits,
+            max_integer_digits,
             precision,
+            min_fraction_digits,
+            max_fraction_digits,
+            force_decimal_point,
             format,
+            notation,
+            exponent_style,
             separator,
             spacing,
             decimal_separator,
+            ratio,
+            scale,
+            currency,
+            currency_display,
+            prefix,
+            suffix,
         } = self;
+        // a spacing of 0 disables grouping
+        let (separator, spacing) = match spacing {
+            Some(0) => (None, None),
+            _ => (separator, spacing),
+        };
         NumFmt {
             fill,
+            right_fill,
             align,
             sign,
+            zero_sign,
+            minus_sign,
+            plus_sign,
             hash,
+            base_marker,
             zero,
             width,
+            width_unit,
+            max_width,
+            overflow,
+            min_integer_digits,
+            max_integer_digits,
             precision,
+            min_fraction_digits,
+            max_fraction_digits,
+            force_decimal_point,
             base: format,
+            notation,
+            exponent_style,
             separator,
             spacing,
             decimal_separator,
+            ratio,
+            scale,
+            currency,
+            currency_display,
+            prefix,
+            suffix,
         }
     }
 
@@ -70,7 +143,66 @@ impl Builder {
     /// ```
     #[inline]
     pub fn fill(mut self, param: char) -> Self {
-        self.fill = Some(param);
+        self.fill = Some(param.into());
+        self
+    }
+
+    /// When `width` is greater than the actual rendered width of the number, the excess is padded
+    /// by repeating this pattern.
+    ///
+    /// The pattern is anchored to the start of the rendered output, not to the start of the
+    /// padding, so that the padding of numbers formatted to the same width lines up in columns.
+    /// This makes it possible to render dotted leaders, i.e. for a table of contents:
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{Align, NumFmt};
+    /// let fmt = NumFmt::builder()
+    ///     .prefix("Chapter ")
+    ///     .align(Align::AfterSign)
+    ///     .fill_pattern(". ")
+    ///     .width(16)
+    ///     .build();
+    /// assert_eq!(fmt.fmt(1).unwrap(),   "Chapter . . . .1");
+    /// assert_eq!(fmt.fmt(123).unwrap(), "Chapter . . .123");
+    /// ```
+    ///
+    /// An empty pattern is treated as a single space.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn fill_pattern(mut self, param: impl Into<String>) -> Self {
+        let param = param.into();
+        self.fill = if param.is_empty() {
+            None
+        } else {
+            Some(param.into())
+        };
+        self
+    }
+
+    /// Pad the excess space after the number by repeating this pattern.
+    ///
+    /// Only `Align::Left` and `Align::Center` produce padding after the number. By default, it
+    /// is padded with the same pattern as the space before the number.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{Align, NumFmt};
+    /// let fmt = NumFmt::builder()
+    ///     .align(Align::Center)
+    ///     .fill('>')
+    ///     .right_fill_pattern("<")
+    ///     .width(7)
+    ///     .build();
+    /// assert_eq!(fmt.fmt(123).unwrap(), ">>123<<");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn right_fill_pattern(mut self, param: impl Into<String>) -> Self {
+        let param = param.into();
+        self.right_fill = if param.is_empty() {
+            None
+        } else {
+            Some(param.into())
+        };
         self
     }
 
@@ -88,6 +220,64 @@ impl Builder {
         self
     }
 
+    /// Set the rendering of the sign of numbers which render as zero. See [`ZeroSign`].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{NumFmt, Sign, WidthUnit, ZeroSign};
+    /// let fmt = NumFmt::builder().precision(Some(1)).zero_sign(ZeroSign::Positive).build();
+    /// assert_eq!(fmt.fmt(-0.01).unwrap(), "0.0");
+    ///
+    /// let fmt = NumFmt::builder().zero_sign(ZeroSign::Preserve).build();
+    /// assert_eq!(fmt.fmt(-0.0).unwrap(), "-0");
+    ///
+    /// let fmt = NumFmt::builder().sign(Sign::PlusAndMinus).zero_sign(ZeroSign::PlusMinus).build();
+    /// assert_eq!(fmt.fmt(0).unwrap(), "±0");
+    /// ```
+    #[inline]
+    pub fn zero_sign(mut self, param: ZeroSign) -> Self {
+        self.zero_sign = param;
+        self
+    }
+
+    /// Set the `char` used as the minus sign.
+    ///
+    /// This can be desirable for typographic output, which prefers U+2212 MINUS SIGN over the
+    /// ASCII hyphen-minus, or for dashboards, which might indicate a decrease with a `▼`.
+    ///
+    /// The minus sign is used by [`Sign::OnlyMinus`], [`Sign::PlusAndMinus`],
+    /// [`Sign::SpaceAndMinus`] and [`Sign::TrailingMinus`].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::builder().minus_sign('\u{2212}').width(4).build();
+    /// assert_eq!(fmt.fmt(-12).unwrap(), " \u{2212}12");
+    /// ```
+    #[inline]
+    pub fn minus_sign(mut self, param: char) -> Self {
+        self.minus_sign = Some(param);
+        self
+    }
+
+    /// Set the `char` used as the plus sign.
+    ///
+    /// The plus sign is used only by [`Sign::PlusAndMinus`].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{NumFmt, Sign};
+    /// let fmt = NumFmt::builder()
+    ///     .sign(Sign::PlusAndMinus)
+    ///     .plus_sign('▲')
+    ///     .minus_sign('▼')
+    ///     .build();
+    /// assert_eq!(fmt.fmt(12).unwrap(), "▲12");
+    /// assert_eq!(fmt.fmt(-12).unwrap(), "▼12");
+    /// ```
+    #[inline]
+    pub fn plus_sign(mut self, param: char) -> Self {
+        self.plus_sign = Some(param);
+        self
+    }
+
     /// If a `set`, print a base specification before the number
     /// according to its format.
     ///
@@ -105,13 +295,44 @@ impl Builder {
         self
     }
 
+    /// Set the style of the base specification printed when [`Builder::hash`] is set.
+    /// See [`BaseMarker`].
+    ///
+    /// Like the default base specification, the marker counts toward the `width`, and the
+    /// zero handler pads between the marker and the number.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{Base, BaseMarker, NumFmt};
+    /// let fmt = NumFmt::builder()
+    ///     .base(Base::UpperHex)
+    ///     .hash(true)
+    ///     .base_marker(BaseMarker::Intel)
+    ///     .build();
+    /// assert_eq!(fmt.fmt(31).unwrap(), "1Fh");
+    ///
+    /// let fmt = NumFmt::builder()
+    ///     .base(Base::LowerHex)
+    ///     .hash(true)
+    ///     .base_marker(BaseMarker::Custom { prefix: "#".into(), suffix: "".into() })
+    ///     .zero(true)
+    ///     .width(7)
+    ///     .build();
+    /// assert_eq!(fmt.fmt(0xff00).unwrap(), "#00ff00");
+    /// ```
+    #[inline]
+    pub fn base_marker(mut self, param: BaseMarker) -> Self {
+        self.base_marker = param;
+        self
+    }
+
     /// If `set`, engage the zero handler.
     ///
     /// The zero handler overrides the padding specification to `0`, and
     /// treats pad characters as part of the number, in contrast
     /// to the default behavior which treats them as arbitrary spacing.
     ///
-    /// Only valid with `Align::Right` and `Align::Decimal`.
+    /// Only valid with `Align::Right`, `Align::Decimal` and `Align::AfterSign`. To pad between
+    /// the sign and the digits with another character, use `Align::AfterSign`.
     ///
     /// ## Examples
     ///
@@ -131,7 +352,7 @@ impl Builder {
     #[inline]
     pub fn zero(mut self, set: bool) -> Self {
         if set {
-            self.fill = Some('0');
+            self.fill = Some('0'.into());
             self.zero = true;
         } else {
             self.fill = None;
@@ -140,6 +361,40 @@ impl Builder {
         self
     }
 
+    /// Set literal text to print before the number.
+    ///
+    /// The prefix precedes the sign and base specification, and counts toward the `width`.
+    /// Padding is printed before the prefix, except by the zero handler, which pads between
+    /// the base specification and the number.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::builder().prefix("~").width(5).build();
+    /// assert_eq!(fmt.fmt(-12).unwrap(), " ~-12");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn prefix(mut self, param: impl Into<String>) -> Self {
+        self.prefix = Some(param.into().into());
+        self
+    }
+
+    /// Set literal text to print after the number, i.e. a unit.
+    ///
+    /// The suffix follows all other parts of the number, and counts toward the `width`.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::builder().suffix("°C").precision(Some(1)).width(7).build();
+    /// assert_eq!(fmt.fmt(-4.25).unwrap(), " -4.2°C");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn suffix(mut self, param: impl Into<String>) -> Self {
+        self.suffix = Some(param.into().into());
+        self
+    }
+
     /// Set the `width` parameter.
     ///
     /// This is a parameter for the "minimum width" that the format should take up. If
@@ -170,6 +425,101 @@ impl Builder {
         self
     }
 
+    /// Set the unit in which `width` and padding are measured. See [`WidthUnit`].
+    ///
+    /// By default, every `char` counts as one unit of width. For output to a terminal, it can
+    /// be preferable to measure in columns, so that wide characters and combining characters
+    /// don't misalign the output.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{NumFmt, WidthUnit};
+    /// let fmt = NumFmt::builder()
+    ///     .fill('🖤')
+    ///     .width(6)
+    ///     .width_unit(WidthUnit::Columns)
+    ///     .build();
+    /// // each heart takes up two columns
+    /// assert_eq!(fmt.fmt(12).unwrap(), "🖤🖤12");
+    /// ```
+    #[inline]
+    pub fn width_unit(mut self, param: WidthUnit) -> Self {
+        self.width_unit = param;
+        self
+    }
+
+    /// Set the maximum width of the rendered number, in the configured
+    /// [`width_unit`][Builder::width_unit].
+    ///
+    /// The [`overflow`][Builder::overflow] policy determines what happens when a number does not
+    /// fit. A `width` greater than the maximum width is reduced to the maximum width.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{NumFmt, Overflow};
+    /// let fmt = NumFmt::builder()
+    ///     .width(6)
+    ///     .max_width(Some(6))
+    ///     .overflow(Overflow::Fill)
+    ///     .build();
+    /// assert_eq!(fmt.fmt(12345).unwrap(), " 12345");
+    /// assert_eq!(fmt.fmt(1234567).unwrap(), "######");
+    /// ```
+    #[inline]
+    pub fn max_width(mut self, param: Option<usize>) -> Self {
+        self.max_width = param;
+        self
+    }
+
+    /// Set the policy applied when the rendered number exceeds the
+    /// [`max_width`][Builder::max_width]. See [`Overflow`].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{NumFmt, Overflow};
+    /// let fmt = NumFmt::builder()
+    ///     .max_width(Some(8))
+    ///     .overflow(Overflow::Scientific)
+    ///     .build();
+    /// assert_eq!(fmt.fmt(1234.5).unwrap(), "1234.5");
+    /// assert_eq!(fmt.fmt(123456789012_u64).unwrap(), "1.234e11");
+    /// ```
+    #[inline]
+    pub fn overflow(mut self, param: Overflow) -> Self {
+        self.overflow = param;
+        self
+    }
+
+    /// Set the minimum count of digits before the decimal.
+    ///
+    /// Numbers with fewer integer digits are padded with leading zeros, like C's `%.5d`.
+    /// Unlike the [`zero`][Builder::zero] handler, this is independent of the `width`, so the
+    /// number can still be padded with the fill:
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::builder().min_integer_digits(Some(5)).width(10).build();
+    /// assert_eq!(fmt.fmt(-42).unwrap(), "    -00042");
+    /// ```
+    #[inline]
+    pub fn min_integer_digits(mut self, param: Option<usize>) -> Self {
+        self.min_integer_digits = param;
+        self
+    }
+
+    /// Set the maximum count of digits before the decimal.
+    ///
+    /// Numbers with more integer digits keep only the low-order digits, like an odometer.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::builder().max_integer_digits(Some(3)).build();
+    /// assert_eq!(fmt.fmt(123456).unwrap(), "456");
+    /// assert_eq!(fmt.fmt(12.5).unwrap(), "12.5");
+    /// ```
+    #[inline]
+    pub fn max_integer_digits(mut self, param: Option<usize>) -> Self {
+        self.max_integer_digits = param;
+        self
+    }
+
     /// Set the `precision` parameter.
     ///
     /// How many digits after the decimal point are printed. Note that integers can be forced
@@ -197,6 +547,64 @@ impl Builder {
         self
     }
 
+    /// Set the minimum count of digits after the decimal.
+    ///
+    /// Fractions are padded with zeros up to this count, and trailing zeros beyond it are
+    /// trimmed. If unset, the minimum is the `precision`. Combined with the `precision`, this
+    /// trims trailing zeros:
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::builder()
+    ///     .precision(Some(2))
+    ///     .min_fraction_digits(Some(0))
+    ///     .build();
+    /// assert_eq!(fmt.fmt(1.5).unwrap(), "1.5");
+    /// assert_eq!(fmt.fmt(2.0).unwrap(), "2");
+    /// assert_eq!(fmt.fmt(3.14159).unwrap(), "3.14");
+    /// ```
+    #[inline]
+    pub fn min_fraction_digits(mut self, param: Option<usize>) -> Self {
+        self.min_fraction_digits = param;
+        self
+    }
+
+    /// Set the maximum count of digits after the decimal.
+    ///
+    /// If unset, the maximum is the `precision`. Together with the
+    /// [minimum][Builder::min_fraction_digits], this expresses patterns such as ICU's `0.00##`:
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::builder()
+    ///     .min_fraction_digits(Some(2))
+    ///     .max_fraction_digits(Some(4))
+    ///     .build();
+    /// assert_eq!(fmt.fmt(1).unwrap(), "1.00");
+    /// assert_eq!(fmt.fmt(1.125).unwrap(), "1.125");
+    /// assert_eq!(fmt.fmt(3.14159).unwrap(), "3.1415");
+    /// ```
+    #[inline]
+    pub fn max_fraction_digits(mut self, param: Option<usize>) -> Self {
+        self.max_fraction_digits = param;
+        self
+    }
+
+    /// Print the decimal point even when no digits follow it, like the alternate form of C's
+    /// `%#.0f`.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::builder().force_decimal_point(true).build();
+    /// assert_eq!(fmt.fmt(2).unwrap(), "2.");
+    /// assert_eq!(fmt.fmt(2.5).unwrap(), "2.5");
+    /// ```
+    #[inline]
+    pub fn force_decimal_point(mut self, param: bool) -> Self {
+        self.force_decimal_point = param;
+        self
+    }
+
     /// Set the output format.
     ///
     /// See [`Base`].
@@ -206,6 +614,37 @@ impl Builder {
         self
     }
 
+    /// Set how the magnitude of the number is written. See [`Notation`].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{NumFmt, Notation};
+    /// let fmt = NumFmt::builder().notation(Notation::Scientific).build();
+    /// assert_eq!(fmt.fmt(0.000123).unwrap(), "1.23e-4");
+    /// assert_eq!(fmt.fmt(-4500).unwrap(), "-4.5e3");
+    /// ```
+    #[inline]
+    pub fn notation(mut self, param: Notation) -> Self {
+        self.notation = param;
+        self
+    }
+
+    /// Set how the exponent of a number in scientific notation is written.
+    /// See [`ExponentStyle`].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{ExponentStyle, NumFmt, Notation};
+    /// let fmt = NumFmt::builder()
+    ///     .notation(Notation::Scientific)
+    ///     .exponent_style(ExponentStyle::Superscript)
+    ///     .build();
+    /// assert_eq!(fmt.fmt(0.000123).unwrap(), "1.23×10⁻⁴");
+    /// ```
+    #[inline]
+    pub fn exponent_style(mut self, param: ExponentStyle) -> Self {
+        self.exponent_style = param;
+        self
+    }
+
     /// Set the separator.
     ///
     /// A separator is a (typically non-numeric) character inserted between groups of digits to make
@@ -221,6 +660,8 @@ impl Builder {
     ///
     /// Spacing determines the number of characters in each character group. It is only
     /// of interest when the separator is set. The default spacing is 3.
+    ///
+    /// A spacing of 0 disables grouping, even if the separator is set.
     #[inline]
     pub fn spacing(mut self, param: usize) -> Self {
         self.spacing = Some(param);
@@ -236,36 +677,151 @@ impl Builder {
         self.decimal_separator = Some(param);
         self
     }
+
+    /// Render the number as a ratio. See [`Ratio`].
+    ///
+    /// The ratio's symbol is appended to the number and counts toward its `width`.
+    /// Only compatible with [`Base::Decimal`].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{NumFmt, Ratio};
+    /// let fmt = NumFmt::builder().ratio(Some(Ratio::Percent)).precision(Some(1)).build();
+    /// assert_eq!(fmt.fmt(0.1234).unwrap(), "12.3%");
+    /// ```
+    #[inline]
+    pub fn ratio(mut self, param: Option<Ratio>) -> Self {
+        self.ratio = param;
+        self
+    }
+
+    /// Set the scale: multiply the number by `10**param` before rendering.
+    ///
+    /// Scaling shifts the decimal point within the number's digits; it never performs
+    /// lossy arithmetic. It combines with any scaling implied by [`Builder::ratio`]; a total
+    /// beyond ±400 fails with [`Error::ScaleOutOfRange`][crate::Error::ScaleOutOfRange].
+    /// Only compatible with [`Base::Decimal`].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// // display in thousands
+    /// let fmt = NumFmt::builder().scale(-3).separator(Some(',')).build();
+    /// assert_eq!(fmt.fmt(12_345_678).unwrap(), "12,345.678");
+    /// ```
+    #[inline]
+    pub fn scale(mut self, param: i32) -> Self {
+        self.scale = param;
+        self
+    }
+
+    /// Render the number as an amount of a currency. See [`Currency`].
+    ///
+    /// The currency's marker is placed according to its conventions and counts toward the
+    /// `width`. Unless a precision is set explicitly, the currency's minor units determine
+    /// the precision.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{Currency, NumFmt};
+    /// let jpy = Currency::from_code("JPY").unwrap();
+    /// let fmt = NumFmt::builder().currency(Some(jpy)).width(8).build();
+    /// assert_eq!(fmt.fmt(1234).unwrap(), "   ¥1234");
+    ///
+    /// let eur = Currency::from_code("EUR").unwrap();
+    /// let fmt = NumFmt::builder().currency(Some(eur)).decimal_separator(',').build();
+    /// assert_eq!(fmt.fmt(-3.5).unwrap(), "-3,50 €");
+    /// ```
+    #[inline]
+    pub fn currency(mut self, param: Option<Currency>) -> Self {
+        self.currency = param;
+        self
+    }
+
+    /// Set which marker identifies the currency. See [`CurrencyDisplay`].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{Currency, CurrencyDisplay, NumFmt};
+    /// let fmt = NumFmt::builder()
+    ///     .currency(Currency::from_code("KWD"))
+    ///     .currency_display(CurrencyDisplay::Code)
+    ///     .build();
+    /// assert_eq!(fmt.fmt(2.5).unwrap(), "KWD 2.500");
+    /// ```
+    #[inline]
+    pub fn currency_display(mut self, param: CurrencyDisplay) -> Self {
+        self.currency_display = param;
+        self
+    }
 }
 
 impl From<NumFmt> for Builder {
     fn from(
         NumFmt {
             fill,
+            right_fill,
             align,
             sign,
+            zero_sign,
+            minus_sign,
+            plus_sign,
             hash,
+            base_marker,
             zero,
             width,
+            width_unit,
+            max_width,
+            overflow,
+            min_integer_digits,
+            max_integer_digits,
             precision,
+            min_fraction_digits,
+            max_fraction_digits,
+            force_decimal_point,
             base: format,
+            notation,
+            exponent_style,
             separator,
             spacing,
             decimal_separator,
+            ratio,
+            scale,
+            currency,
+            currency_display,
+            prefix,
+            suffix,
         }: NumFmt,
     ) -> Self {
         Builder {
             fill,
+            right_fill,
             align,
             sign,
+            zero_sign,
+            minus_sign,
+            plus_sign,
             hash,
+            base_marker,
             zero,
             width,
+            width_unit,
+            max_width,
+            overflow,
+            min_integer_digits,
+            max_integer_digits,
             precision,
+            min_fraction_digits,
+            max_fraction_digits,
+            force_decimal_point,
             format,
+            notation,
+            exponent_style,
             separator,
             spacing,
             decimal_separator,
+            ratio,
+            scale,
+            currency,
+            currency_display,
+            prefix,
+            suffix,
         }
     }
 }
@@ -0,0 +1,163 @@
+/// Metadata describing how amounts of a currency are conventionally written.
+///
+/// Common currencies can be looked up by their ISO 4217 code with [`Currency::from_code`].
+/// Their conventions follow the currency's primary locale. All fields are public, so it is
+/// straightforward to adjust the conventions or to describe a currency not in the table.
+///
+/// ```rust
+/// # use num_runtime_fmt::{Currency, NumFmt};
+/// let usd = Currency::from_code("USD").unwrap();
+/// let fmt = NumFmt::builder().currency(Some(usd)).separator(Some(',')).build();
+/// assert_eq!(fmt.fmt(-1234.5).unwrap(), "-$1,234.50");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Currency {
+    /// ISO 4217 alphabetic code, i.e. `"USD"`.
+    pub code: &'static str,
+    /// Symbol conventionally used for this currency, i.e. `"$"`.
+    pub symbol: &'static str,
+    /// Number of digits after the decimal in the currency's minor unit.
+    ///
+    /// This is the default precision when formatting amounts of this currency.
+    pub minor_units: usize,
+    /// Whether the symbol precedes or follows the amount.
+    pub position: SymbolPosition,
+    /// Whether a space separates the symbol from the amount.
+    pub spaced: bool,
+    /// Whether the sign of a negative amount precedes the symbol (`-$1.00`) or follows it
+    /// (`$-1.00`). Only relevant when the symbol precedes the amount.
+    pub sign_before_symbol: bool,
+}
+
+/// Placement of a currency marker relative to the amount.
+///
+/// - `Prefix`: the marker precedes the amount, i.e. `$1.00`
+/// - `Suffix`: the marker follows the amount, i.e. `1.00 €`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SymbolPosition {
+    Prefix,
+    Suffix,
+}
+
+/// Which marker identifies the currency of an amount.
+///
+/// - `Symbol`: the currency's symbol, i.e. `$1.00` (default)
+/// - `Code`: the currency's ISO 4217 code, i.e. `USD 1.00`. Codes are always separated from
+///   the amount by a space.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CurrencyDisplay {
+    Symbol,
+    Code,
+}
+
+impl Default for CurrencyDisplay {
+    #[inline]
+    fn default() -> Self {
+        Self::Symbol
+    }
+}
+
+macro_rules! currencies {
+    ($( $code:literal, $symbol:literal, $minor:literal, $position:ident, $spaced:literal, $sign_before:literal; )+) => {
+        const CURRENCIES: &[Currency] = &[
+            $(
+                Currency {
+                    code: $code,
+                    symbol: $symbol,
+                    minor_units: $minor,
+                    position: SymbolPosition::$position,
+                    spaced: $spaced,
+                    sign_before_symbol: $sign_before,
+                },
+            )+
+        ];
+    };
+}
+
+// code, symbol, minor units, position, spaced, sign before symbol
+currencies! {
+    "AED", "AED", 2, Prefix, true, true;
+    "ARS", "$", 2, Prefix, true, true;
+    "AUD", "$", 2, Prefix, false, true;
+    "BHD", "BHD", 3, Prefix, true, true;
+    "BRL", "R$", 2, Prefix, true, true;
+    "CAD", "$", 2, Prefix, false, true;
+    "CHF", "CHF", 2, Prefix, true, false;
+    "CLP", "$", 0, Prefix, false, true;
+    "CNY", "¥", 2, Prefix, false, true;
+    "CZK", "Kč", 2, Suffix, true, true;
+    "DKK", "kr.", 2, Suffix, true, true;
+    "EUR", "€", 2, Suffix, true, true;
+    "GBP", "£", 2, Prefix, false, true;
+    "HKD", "HK$", 2, Prefix, false, true;
+    "HUF", "Ft", 2, Suffix, true, true;
+    "IDR", "Rp", 2, Prefix, false, true;
+    "ILS", "₪", 2, Suffix, true, true;
+    "INR", "₹", 2, Prefix, false, true;
+    "ISK", "kr", 0, Suffix, true, true;
+    "JOD", "JOD", 3, Prefix, true, true;
+    "JPY", "¥", 0, Prefix, false, true;
+    "KRW", "₩", 0, Prefix, false, true;
+    "KWD", "KWD", 3, Prefix, true, true;
+    "MXN", "$", 2, Prefix, false, true;
+    "NOK", "kr", 2, Suffix, true, true;
+    "NZD", "$", 2, Prefix, false, true;
+    "OMR", "OMR", 3, Prefix, true, true;
+    "PLN", "zł", 2, Suffix, true, true;
+    "RUB", "₽", 2, Suffix, true, true;
+    "SAR", "SAR", 2, Prefix, true, true;
+    "SEK", "kr", 2, Suffix, true, true;
+    "SGD", "$", 2, Prefix, false, true;
+    "THB", "฿", 2, Prefix, false, true;
+    "TND", "TND", 3, Prefix, true, true;
+    "TRY", "₺", 2, Prefix, false, true;
+    "TWD", "$", 2, Prefix, false, true;
+    "USD", "$", 2, Prefix, false, true;
+    "VND", "₫", 0, Suffix, true, true;
+    "ZAR", "R", 2, Prefix, false, true;
+}
+
+impl Currency {
+    /// Look up a currency by its ISO 4217 alphabetic code.
+    ///
+    /// The lookup is case-insensitive. Returns `None` for codes not in the crate's table.
+    pub fn from_code(code: &str) -> Option<Currency> {
+        CURRENCIES
+            .iter()
+            .find(|currency| currency.code.eq_ignore_ascii_case(code))
+            .copied()
+    }
+
+    /// The marker identifying this currency, and whether it is separated from the amount
+    /// by a space.
+    pub(crate) fn marker(&self, display: CurrencyDisplay) -> (&'static str, bool) {
+        match display {
+            CurrencyDisplay::Symbol => (self.symbol, self.spaced),
+            CurrencyDisplay::Code => (self.code, true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_sorted_and_unique() {
+        for pair in CURRENCIES.windows(2) {
+            assert!(
+                pair[0].code < pair[1].code,
+                "{} >= {}",
+                pair[0].code,
+                pair[1].code
+            );
+        }
+    }
+
+    #[test]
+    fn lookup_is_case_insensitive() {
+        assert_eq!(Currency::from_code("jpy"), Currency::from_code("JPY"));
+        assert_eq!(Currency::from_code("JPY").unwrap().minor_units, 0);
+        assert!(Currency::from_code("XXX").is_none());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{part::FmtSink, Dynamic, Error, NumFmt, Numeric};
+use core::fmt;
+
+/// What a [`DisplayNum`] writes when the number cannot be formatted.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum OnError<'a> {
+    Fill,
+    Fail,
+    Text(&'a str),
+}
+
+/// Adapter which formats a number according to a [`NumFmt`] when displayed.
+///
+/// Created by [`NumFmt::display`] and [`NumFmt::display_with`].
+///
+/// ```rust
+/// # use num_runtime_fmt::NumFmt;
+/// let fmt = NumFmt::from_str(",").unwrap();
+/// assert_eq!(format!("total: {}", fmt.display(1234567)), "total: 1,234,567");
+/// ```
+///
+/// `Display` cannot report a formatting [`Error`], so when the number cannot be formatted, the
+/// output is filled with `#` to the configured width (at least one), or to the maximum width if
+/// the number overflows it, in the manner of a spreadsheet. Use
+/// [`fallback`][DisplayNum::fallback] to render other text instead, or
+/// [`strict`][DisplayNum::strict] to return a [`fmt::Error`]. Errors of the underlying writer
+/// are always returned.
+///
+/// ```rust
+/// # use num_runtime_fmt::NumFmt;
+/// let fmt = NumFmt::from_str(">4b").unwrap();
+/// assert_eq!(fmt.display(1.5).to_string(), "####");
+/// assert_eq!(fmt.display(1.5).fallback("n/a").to_string(), "n/a");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayNum<'a, N> {
+    fmt: &'a NumFmt,
+    number: N,
+    dynamic: Dynamic,
+    on_error: OnError<'a>,
+}
+
+impl<'a, N> DisplayNum<'a, N> {
+    pub(crate) fn new(fmt: &'a NumFmt, number: N, dynamic: Dynamic) -> Self {
+        DisplayNum {
+            fmt,
+            number,
+            dynamic,
+            on_error: OnError::Fill,
+        }
+    }
+
+    /// Render `text` when the number cannot be formatted.
+    pub fn fallback(mut self, text: &'a str) -> Self {
+        self.on_error = OnError::Text(text);
+        self
+    }
+
+    /// Return a [`fmt::Error`] when the number cannot be formatted.
+    ///
+    /// Note that `format!` and `to_string` panic when a `Display` implementation returns an
+    /// error.
+    pub fn strict(mut self) -> Self {
+        self.on_error = OnError::Fail;
+        self
+    }
+}
+
+impl<N: Numeric> fmt::Display for DisplayNum<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        display_into(self.fmt, &self.number, self.dynamic, self.on_error, f)
+    }
+}
+
+/// Format `number` into `f`, handling formatting errors according to `on_error`.
+pub(crate) fn display_into<N: Numeric>(
+    fmt: &NumFmt,
+    number: &N,
+    dynamic: Dynamic,
+    on_error: OnError<'_>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let mut sink = FmtSink::new(f);
+    let formatted = fmt.fmt_into(number, dynamic, &mut sink);
+    sink.result?;
+    // formatting errors are detected before anything is written
+    match (formatted, on_error) {
+        (Ok(()), _) => Ok(()),
+        (Err(_), OnError::Fail) => Err(fmt::Error),
+        (Err(_), OnError::Text(text)) => f.write_str(text),
+        (Err(err), OnError::Fill) => {
+            let width = match err {
+                Error::Overflow(_, max_width) => max_width,
+                _ => fmt.width_with(dynamic).max(1),
+            };
+            for _ in 0..width {
+                f.write_str("#")?;
+            }
+            Ok(())
+        }
+    }
+}
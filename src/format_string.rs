@@ -0,0 +1,255 @@
+use crate::{
+    Align, Base, BaseMarker, CurrencyDisplay, ExponentStyle, Notation, NumFmt, Overflow, Ratio,
+    Sign, WidthUnit, ZeroSign,
+};
+use core::fmt;
+
+/// A setting of a [`NumFmt`] which the format string grammar cannot express.
+///
+/// Returned by [`NumFmt::format_string`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Unrepresentable {
+    /// A fill of more than one `char`.
+    FillPattern,
+    /// A newline as the fill.
+    NewlineFill,
+    /// A right fill.
+    RightFill,
+    /// The zero flag with a fill other than `0`.
+    ZeroFill,
+    /// The zero flag without a width.
+    ZeroWithoutWidth,
+    /// Custom minus or plus signs.
+    SignChars,
+    /// [`ZeroSign::Preserve`] or [`ZeroSign::Unsigned`].
+    ZeroSign(ZeroSign),
+    /// A base marker other than [`BaseMarker::Rust`].
+    BaseMarker,
+    /// A width unit other than [`WidthUnit::Chars`].
+    WidthUnit,
+    /// A maximum width, or an overflow strategy.
+    MaxWidth,
+    /// Minimum or maximum integer digits.
+    IntegerDigits,
+    /// Minimum or maximum fraction digits, or a forced decimal point.
+    FractionDigits,
+    /// A combination of base, notation, exponent style and ratio which no single format type
+    /// expresses.
+    Format,
+    /// A group separator other than `_`, `,` or ` `.
+    Separator(char),
+    /// A spacing without a separator.
+    Spacing,
+    /// A decimal separator.
+    DecimalSeparator,
+    /// A scale.
+    Scale,
+    /// A currency.
+    Currency,
+    /// A prefix or suffix.
+    Affix,
+}
+
+impl fmt::Display for Unrepresentable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FillPattern => f.write_str("a fill pattern")?,
+            Self::NewlineFill => f.write_str("a newline fill")?,
+            Self::RightFill => f.write_str("a right fill")?,
+            Self::ZeroFill => f.write_str("the zero flag with a fill other than `0`")?,
+            Self::ZeroWithoutWidth => f.write_str("the zero flag without a width")?,
+            Self::SignChars => f.write_str("custom sign chars")?,
+            Self::ZeroSign(zero_sign) => write!(f, "ZeroSign::{:?}", zero_sign)?,
+            Self::BaseMarker => f.write_str("a custom base marker")?,
+            Self::WidthUnit => f.write_str("a width unit")?,
+            Self::MaxWidth => f.write_str("a maximum width")?,
+            Self::IntegerDigits => f.write_str("a count of integer digits")?,
+            Self::FractionDigits => f.write_str("a count of fraction digits")?,
+            Self::Format => f.write_str("this combination of base, notation and ratio")?,
+            Self::Separator(separator) => write!(f, "the separator {:?}", separator)?,
+            Self::Spacing => f.write_str("a spacing without a separator")?,
+            Self::DecimalSeparator => f.write_str("a decimal separator")?,
+            Self::Scale => f.write_str("a scale")?,
+            Self::Currency => f.write_str("a currency")?,
+            Self::Affix => f.write_str("a prefix or suffix")?,
+        }
+        f.write_str(" can't be expressed in a format string")
+    }
+}
+
+/// The canonical format string of a [`NumFmt`]. Created by [`NumFmt::format_string`].
+///
+/// Parsing the displayed string produces a `NumFmt` equal to the original:
+///
+/// ```rust
+/// # use num_runtime_fmt::{Align, Base, NumFmt};
+/// let fmt = NumFmt::builder()
+///     .align(Align::Center)
+///     .hash(true)
+///     .width(10)
+///     .base(Base::LowerHex)
+///     .separator(Some('_'))
+///     .spacing(4)
+///     .build();
+/// let format_string = fmt.format_string().unwrap().to_string();
+/// assert_eq!(format_string, "^#10x_4");
+/// assert_eq!(NumFmt::from_str(&format_string).unwrap(), fmt);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FormatString<'a> {
+    fmt: &'a NumFmt,
+    format: &'static str,
+}
+
+impl<'a> FormatString<'a> {
+    pub(crate) fn new(fmt: &'a NumFmt) -> Result<Self, Unrepresentable> {
+        let fill = fmt.fill.as_deref();
+        if fill
+            .map(|fill| fill.chars().count() > 1)
+            .unwrap_or_default()
+        {
+            return Err(Unrepresentable::FillPattern);
+        }
+        if fill == Some("\n") {
+            return Err(Unrepresentable::NewlineFill);
+        }
+        if fmt.right_fill.is_some() {
+            return Err(Unrepresentable::RightFill);
+        }
+        if fmt.zero && fill != Some("0") {
+            return Err(Unrepresentable::ZeroFill);
+        }
+        if fmt.zero && fmt.width() == 0 {
+            return Err(Unrepresentable::ZeroWithoutWidth);
+        }
+        if fmt.minus_sign.is_some() || fmt.plus_sign.is_some() {
+            return Err(Unrepresentable::SignChars);
+        }
+        if let zero_sign @ (ZeroSign::Preserve | ZeroSign::Unsigned) = fmt.zero_sign() {
+            return Err(Unrepresentable::ZeroSign(zero_sign));
+        }
+        if *fmt.base_marker() != BaseMarker::Rust {
+            return Err(Unrepresentable::BaseMarker);
+        }
+        if fmt.width_unit() != WidthUnit::Chars {
+            return Err(Unrepresentable::WidthUnit);
+        }
+        if fmt.max_width().is_some() || fmt.overflow() != Overflow::Extend {
+            return Err(Unrepresentable::MaxWidth);
+        }
+        if fmt.min_integer_digits.is_some() || fmt.max_integer_digits.is_some() {
+            return Err(Unrepresentable::IntegerDigits);
+        }
+        if fmt.min_fraction_digits.is_some()
+            || fmt.max_fraction_digits.is_some()
+            || fmt.force_decimal_point()
+        {
+            return Err(Unrepresentable::FractionDigits);
+        }
+        let format = match (fmt.base(), fmt.notation(), fmt.exponent_style, fmt.ratio()) {
+            (base, Notation::Positional, ExponentStyle::LowerE, None) => match base {
+                Base::Binary => "b",
+                Base::Octal => "o",
+                Base::Decimal => "",
+                Base::LowerHex => "x",
+                Base::UpperHex => "X",
+            },
+            (Base::Decimal, Notation::Scientific, ExponentStyle::LowerE, None) => "e",
+            (Base::Decimal, Notation::Scientific, ExponentStyle::UpperE, None) => "E",
+            (Base::Decimal, Notation::Positional, ExponentStyle::LowerE, Some(ratio)) => {
+                match ratio {
+                    Ratio::Percent => "%",
+                    Ratio::PerMille => "‰",
+                    Ratio::BasisPoint => "bp",
+                }
+            }
+            _ => return Err(Unrepresentable::Format),
+        };
+        match (fmt.separator, fmt.spacing) {
+            (Some('_' | ',' | ' '), _) | (None, None) => {}
+            (Some(separator), _) => return Err(Unrepresentable::Separator(separator)),
+            (None, Some(_)) => return Err(Unrepresentable::Spacing),
+        }
+        if fmt.decimal_separator.is_some() {
+            return Err(Unrepresentable::DecimalSeparator);
+        }
+        if fmt.scale() != 0 {
+            return Err(Unrepresentable::Scale);
+        }
+        if fmt.currency().is_some() || fmt.currency_display() != CurrencyDisplay::Symbol {
+            return Err(Unrepresentable::Currency);
+        }
+        if fmt.prefix().is_some() || fmt.suffix().is_some() {
+            return Err(Unrepresentable::Affix);
+        }
+
+        Ok(FormatString { fmt, format })
+    }
+}
+
+impl fmt::Display for FormatString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fmt = self.fmt;
+
+        // the zero flag implies its fill
+        let fill = fmt.fill.as_deref().filter(|_| !fmt.zero);
+        if let Some(fill) = fill {
+            f.write_str(fill)?;
+        }
+        if fill.is_some() || fmt.align() != Align::Right {
+            f.write_str(match fmt.align() {
+                Align::Left => "<",
+                Align::Center => "^",
+                Align::Right => ">",
+                Align::Decimal => "v",
+                Align::AfterSign => "=",
+            })?;
+        }
+
+        f.write_str(match fmt.sign() {
+            Sign::OnlyMinus => "",
+            Sign::PlusAndMinus => "+",
+            Sign::SpaceAndMinus => " ",
+            Sign::Parentheses => "(",
+            Sign::TrailingMinus => "t",
+            Sign::CreditDebit => "c",
+        })?;
+        f.write_str(match fmt.zero_sign() {
+            ZeroSign::Positive => "z",
+            ZeroSign::PlusMinus => "±",
+            _ => "",
+        })?;
+        if fmt.hash() {
+            f.write_str("#")?;
+        }
+        if fmt.zero {
+            f.write_str("0")?;
+        }
+        if fmt.width() > 0 {
+            write!(f, "{}", fmt.width())?;
+        }
+        if let Some(precision) = fmt.precision() {
+            write!(f, ".{}", precision)?;
+        }
+
+        // A space followed by nothing but digits is a separator rather than a sign. An explicit
+        // decimal format type keeps it a sign.
+        let space_is_separator = fmt.sign() == Sign::SpaceAndMinus
+            && fmt.zero_sign() == ZeroSign::Natural
+            && !fmt.hash()
+            && fmt.precision().is_none()
+            && fmt.separator.is_none();
+        match self.format {
+            "" if space_is_separator => f.write_str("d")?,
+            format => f.write_str(format)?,
+        }
+
+        if let Some(separator) = fmt.separator {
+            write!(f, "{}", separator)?;
+            if let Some(spacing) = fmt.spacing {
+                write!(f, "{}", spacing)?;
+            }
+        }
+        Ok(())
+    }
+}
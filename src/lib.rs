@@ -4,25 +4,38 @@
 //!
 //! The core of this crate is the [`NumFmt`] type. You can [build][NumFmt::builder] it explicitly,
 //! or [parse][NumFmt::from_str] it from a format string with similar grammar to that of the
-//! standard library.
+//! standard library. Going the other way, most configurations have a
+//! [canonical format string][NumFmt::format_string], which parses back into an equal `NumFmt`.
 //!
 //! Given an instance of `NumFmt`, you can call its [`fmt`][NumFmt::fmt] method to simply format
 //! a number, or its [`fmt_with`][NumFmt::fmt_with] method to apply dynamic parameters.
+//! To stream the output into an existing buffer instead, use [`write_to`][NumFmt::write_to] or
+//! [`write_io`][NumFmt::write_io].
+//! To format many numbers in the same way, [`compile`][NumFmt::compile] the configuration into
+//! a [`Plan`] once, up front.
+//! To embed a formatted number in `format!` or `write!`, use [`display`][NumFmt::display], or
+//! [`std_flags`][NumFmt::std_flags] to also honour the flags of the standard library's format
+//! string.
+//! To style the parts of a formatted number differently, [`fmt_to_parts`][NumFmt::fmt_to_parts]
+//! returns it as a sequence of typed spans. For terminals, [`fmt_ansi`][NumFmt::fmt_ansi] styles
+//! the parts with ANSI escape codes, and [`fmt_markup`][NumFmt::fmt_markup] renders them as HTML,
+//! LaTeX or Markdown.
 //!
 //! ## Format String Grammar
 //!
 //! The gramar for the format string derives substantially from the standard library's:
 //!
 //! ```text
-//! format_spec := [[fill]align][sign]['#'][['0']width]['.' precision][format][separator[spacing]]
-//! fill := character
-//! align := '<' | '^' | '>' | 'v'
-//! sign := '+' | '-'
+//! format_spec := [[fill]align][sign][zero_sign]['#'][['0']width]['.' precision][format][separator[spacing]]
+//! fill := character other than '\n'
+//! align := '<' | '^' | '>' | 'v' | '='
+//! sign := '+' | '-' | ' ' | '(' | 't' | 'c'
+//! zero_sign := 'z' | '±'
 //! width := integer not beginning with '0'
 //! precision := integer
-//! format := 'b' | 'o' | 'd' | 'x' | 'X'
+//! format := 'b' | 'o' | 'd' | 'x' | 'X' | 'e' | 'E' | '%' | '‰' | 'bp'
 //! separator := '_', | ',' | ' '
-//! spacing := integer
+//! spacing := integer other than '0'
 //! ```
 //!
 //! ### Note
@@ -39,6 +52,8 @@
 //!
 //! ### Note
 //! Wide characters are counted according to their quantity, not their bit width.
+//! When using the builder, width can instead be [measured][Builder::width_unit] in terminal
+//! columns.
 //!
 //! ```rust
 //! # use num_runtime_fmt::NumFmt;
@@ -51,6 +66,9 @@
 //! assert_eq!(formatted.len(), 9);
 //! ```
 //!
+//! When using the builder, the fill can be a [repeating pattern][Builder::fill_pattern], and
+//! the padding after the number can use a [different pattern][Builder::right_fill_pattern].
+//!
 //! ## `align`ment
 //!
 //! - `>`: the output is right-aligned in `width` columns (default).
@@ -58,12 +76,54 @@
 //! - `<`: the output is left-aligned in `width` columns.
 //! - `v`: attempt to align the decimal point at column index `width`. For integers,
 //!   equivalent to `>`.
+//! - `=`: the output is right-aligned in `width` columns, but the padding is placed after the
+//!   sign and base specification, and before the digits.
+//!
+//! ```rust
+//! # use num_runtime_fmt::NumFmt;
+//! assert_eq!(NumFmt::from_str("=6").unwrap().fmt(-12).unwrap(), "-   12");
+//! assert_eq!(NumFmt::from_str("*=#8x").unwrap().fmt(255).unwrap(), "0x****ff");
+//! ```
 //!
 //! ## `sign`
 //!
 //! - `-`: print a leading `-` for negative numbers, and nothing in particular for
 //!   positive (default)
 //! - `+`: print a leading `+` for positive numbers
+//! - ` ` (space char): print a leading space for positive numbers, so that they line up in
+//!   columns with negative numbers. Because a space is also a separator, a leading space is
+//!   only construed as a sign when the format string cannot be parsed otherwise: ` 5` is a
+//!   space separator with spacing 5, whereas ` 5d` is a space sign with width 5.
+//! - `(`: enclose negative numbers in parentheses, i.e. `(1,234.00)`, as is customary in
+//!   accounting. Positive numbers are followed by a space, so that the closing parenthesis
+//!   lines up in columns.
+//! - `t`: print a trailing `-` for negative numbers, i.e. `1234-`. Positive numbers are
+//!   followed by a space.
+//! - `c`: print a trailing ` CR` (credit) for negative numbers. As is customary, only one side
+//!   is marked: positive numbers are followed by three spaces.
+//!
+//! Trailing signs count toward the `width` of the number, except with `v` alignment, where the
+//! width only covers the portion before the decimal.
+//!
+//! When using the builder, the characters used for the [minus][Builder::minus_sign] and
+//! [plus][Builder::plus_sign] signs can be customized.
+//!
+//! ## `zero_sign`
+//!
+//! Controls the sign of numbers which render as zero, i.e. `-0.001` with a precision of 2.
+//! By default, zero takes the sign of the number it was rendered from.
+//!
+//! - `z`: zero is always non-negative, i.e. `0`, or `+0` with the `+` sign
+//! - `±`: zero is rendered with a leading `±`, i.e. `±0`
+//!
+//! ```rust
+//! # use num_runtime_fmt::NumFmt;
+//! assert_eq!(NumFmt::from_str(".2").unwrap().fmt(-0.001).unwrap(), "-0.00");
+//! assert_eq!(NumFmt::from_str("z.2").unwrap().fmt(-0.001).unwrap(), "0.00");
+//! assert_eq!(NumFmt::from_str("+z.2").unwrap().fmt(-0.001).unwrap(), "+0.00");
+//! ```
+//!
+//! More policies are available via the builder; see [`ZeroSign`].
 //!
 //! ## `#`
 //!
@@ -82,6 +142,15 @@
 //! assert_eq!(NumFmt::from_str("#04b").unwrap().fmt(2).unwrap(), "0b10");
 //! ```
 //!
+//! When using the builder, other [styles of base specification][BaseMarker] are available,
+//! i.e. `0X1F`, `1Fh`, `$1F` or `&H1F`.
+//!
+//! ## Literal affixes
+//!
+//! When using the builder, arbitrary literal text can be printed before
+//! ([`Builder::prefix`]) and after ([`Builder::suffix`]) the number, i.e. to attach a unit.
+//! Affixes count toward the `width` of the number.
+//!
 //! ## `0`
 //!
 //! Engage the zero handler.
@@ -90,6 +159,10 @@
 //! treats pad characters as part of the number, in contrast
 //! to the default behavior which treats them as arbitrary spacing.
 //!
+//! When using the builder, leading zeros can instead be requested independently of the width,
+//! with a [minimum count of integer digits][Builder::min_integer_digits]. A
+//! [maximum count][Builder::max_integer_digits] keeps only the low-order integer digits.
+//!
 //! ## Examples
 //!
 //! ```rust
@@ -123,6 +196,16 @@
 //!
 //! If an explicit width is not provided, defaults to 0.
 //!
+//! When using the builder, a [maximum width][Builder::max_width] can also be set, along with an
+//! [`Overflow`] policy for numbers which don't fit: extend past it, return an error, fill the
+//! space with `#`, truncate with an ellipsis, or fall back to scientific notation.
+//!
+//! ```rust
+//! # use num_runtime_fmt::{NumFmt, Overflow};
+//! let fmt = NumFmt::builder().max_width(Some(5)).overflow(Overflow::Fill).build();
+//! assert_eq!(fmt.fmt(123456).unwrap(), "#####");
+//! ```
+//!
 //! ## `precision`
 //!
 //! Precision will pad or truncate as required if set. If unset, passes through as many
@@ -142,6 +225,11 @@
 //! assert_eq!(NumFmt::from_str("-<6.2").unwrap().fmt(1.0_f32).unwrap(), "1.00--");
 //! ```
 //!
+//! When using the builder, the [minimum][Builder::min_fraction_digits] and
+//! [maximum][Builder::max_fraction_digits] count of digits after the decimal can be set
+//! independently; trailing zeros beyond the minimum are trimmed. The decimal point can also be
+//! [forced][Builder::force_decimal_point] even when no digits follow it.
+//!
 //! ## `format`
 //!
 //! - `b`: Emit this number's binary representation
@@ -149,6 +237,26 @@
 //! - `d`: Emit this number's decimal representation (default)
 //! - `x`: Emit this number's hexadecimal representation with lowercase letters
 //! - `X`: Emit this number's hexadecimal representation with uppercase letters
+//! - `e`: Emit this number's decimal representation in scientific notation, i.e. `1.23e-4`
+//! - `E`: Emit this number's decimal representation in scientific notation, i.e. `1.23E-4`
+//! - `%`: Multiply this number by 100 and emit its decimal representation followed by `%`
+//! - `‰`: Multiply this number by 1000 and emit its decimal representation followed by `‰`
+//! - `bp`: Multiply this number by 10000 and emit its decimal representation followed by `bp`
+//!
+//! The ratio formats scale the number by shifting its decimal digits, so they never
+//! introduce floating-point error. Their symbols count toward the `width` of the number.
+//!
+//! ```rust
+//! # use num_runtime_fmt::NumFmt;
+//! assert_eq!(NumFmt::from_str(".1%").unwrap().fmt(0.1234).unwrap(), "12.3%");
+//! assert_eq!(NumFmt::from_str("6bp").unwrap().fmt(0.0125).unwrap(), " 125bp");
+//! ```
+//!
+//! When using the builder, it is also possible to apply an arbitrary power-of-ten scale,
+//! i.e. to display values in thousands. See [`Builder::scale`].
+//!
+//! The builder also offers typographic [styles][ExponentStyle] for the exponent of scientific
+//! notation, i.e. `1.23×10⁻⁴` or `1.23×10^-4`.
 //!
 //! ### Note
 //!
@@ -179,20 +287,95 @@
 //!
 //! Spacing determines the number of characters in each character group. It is only
 //! of interest when the separator is set. The default spacing is 3.
+//!
+//! ## Currency
+//!
+//! When using the builder, a number can be rendered as an amount of a [`Currency`].
+//! The currency's ISO 4217 metadata determines the default precision and the placement
+//! of its symbol and of the sign. The currency marker counts toward the `width`.
+//!
+//! ```rust
+//! # use num_runtime_fmt::{Currency, NumFmt};
+//! let fmt = NumFmt::builder()
+//!     .currency(Currency::from_code("USD"))
+//!     .separator(Some(','))
+//!     .width(12)
+//!     .build();
+//! assert_eq!(fmt.fmt(-1234.5).unwrap(), "  -$1,234.50");
+//! ```
+//!
+//! ## Features
+//!
+//! The crate supports `no_std` environments. Its features are:
+//!
+//! - `std` (default): [`write_io`][NumFmt::write_io], and the implementations of
+//!   `std::error::Error`. Implies `alloc`.
+//! - `alloc`: the methods returning a `String` or a `Vec`, such as [`fmt`][NumFmt::fmt] and
+//!   [`fmt_to_parts`][NumFmt::fmt_to_parts], the settings holding arbitrary strings, such as
+//!   fill patterns and prefixes, and the implementation of `FromStr`.
+//!
+//! Without any features, a `NumFmt` is parsed or configured with the [`Builder`], and numbers
+//! are rendered with [`write_to`][NumFmt::write_to] or [`display`][NumFmt::display]. These
+//! never allocate, with or without features: the digits are collected in a fixed-capacity
+//! buffer on the stack. Padding and runs of zeros at either end of the digits are not stored,
+//! so this holds the digits of any primitive number. Only a [`Numeric`] implementation
+//! producing more than 128 digits between its first and last nonzero digit exceeds it: with
+//! `alloc`, the rest of its digits spill to the heap; without, it fails with
+//! [`Error::Capacity`].
+//!
+//! The minimum supported Rust version is 1.83.
+
+// unit tests may use the standard library in any configuration
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 mod align;
+#[cfg(feature = "alloc")]
+mod ansi;
 mod base;
+mod buffer;
 mod builder;
+mod currency;
+mod display;
 mod dynamic;
+mod format_string;
+#[cfg(feature = "alloc")]
+mod markup;
+mod notation;
 mod num_fmt;
 pub mod numeric_trait;
+mod overflow;
 pub mod parse;
+mod part;
+mod plan;
+mod ratio;
 mod sign;
+mod std_flags;
+mod text;
+mod width_unit;
 
 pub use align::Align;
-pub use base::Base;
+#[cfg(feature = "alloc")]
+pub use ansi::AnsiStyle;
+pub use base::{Base, BaseMarker};
 pub use builder::Builder;
+pub use currency::{Currency, CurrencyDisplay, SymbolPosition};
+pub use display::DisplayNum;
 pub use dynamic::Dynamic;
+pub use format_string::{FormatString, Unrepresentable};
+#[cfg(feature = "alloc")]
+pub use markup::Markup;
+pub use notation::{ExponentStyle, Notation};
 pub use num_fmt::{Error, NumFmt};
 pub use numeric_trait::Numeric;
-pub use sign::Sign;
+pub use overflow::Overflow;
+#[cfg(feature = "alloc")]
+pub use part::Part;
+pub use part::PartKind;
+pub use plan::Plan;
+pub use ratio::Ratio;
+pub use sign::{Sign, ZeroSign};
+pub use std_flags::StdFlags;
+pub use width_unit::WidthUnit;
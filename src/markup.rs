@@ -0,0 +1,115 @@
+use crate::{notation::split_exponent, Part, PartKind};
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Markup language targeted by [`NumFmt::fmt_markup`].
+///
+/// - `Html`: special characters are escaped as entities, and exponents in any style are rendered
+///   as `×10<sup>-4</sup>`. If `classes` is set, every part is wrapped in a
+///   `<span class="num-…">` named after its [kind][PartKind::name], i.e. `num-group-separator`.
+/// - `Latex`: for math mode. Special characters are escaped, digit groups are separated by a
+///   thin space `\,`, a comma decimal separator is braced as `{,}`, and exponents in any style
+///   are rendered as `\times 10^{-4}`.
+/// - `Markdown`: characters with a meaning in Markdown are backslash-escaped.
+///
+/// Markup does not affect the `width` of the number: padding is computed on the text as it
+/// would be rendered by [`NumFmt::fmt`], in the configured exponent style.
+///
+/// ```rust
+/// # use num_runtime_fmt::{Markup, NumFmt};
+/// let fmt = NumFmt::from_str(".2e").unwrap();
+/// assert_eq!(
+///     fmt.fmt_markup(0.000123, Markup::Html { classes: false }).unwrap(),
+///     "1.23×10<sup>-4</sup>",
+/// );
+/// assert_eq!(fmt.fmt_markup(0.000123, Markup::Latex).unwrap(), r"1.23\times 10^{-4}");
+/// ```
+///
+/// [`NumFmt::fmt_markup`]: crate::NumFmt::fmt_markup
+/// [`NumFmt::fmt`]: crate::NumFmt::fmt
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Markup {
+    Html { classes: bool },
+    Latex,
+    Markdown,
+}
+
+impl Markup {
+    /// Push a part to `out`, marked up for this language.
+    pub(crate) fn push_part(self, part: &Part, out: &mut String) {
+        match self {
+            Self::Html { classes } => {
+                if classes {
+                    out.push_str("<span class=\"num-");
+                    out.push_str(part.kind.name());
+                    out.push_str("\">");
+                }
+                match part.kind {
+                    PartKind::Exponent => {
+                        let (times, exponent) = split_exponent(&part.text);
+                        write!(out, "{}10<sup>{}</sup>", times, exponent)
+                            .expect("writing to a String never fails");
+                    }
+                    _ => escape_html(&part.text, out),
+                }
+                if classes {
+                    out.push_str("</span>");
+                }
+            }
+            Self::Latex => match part.kind {
+                PartKind::GroupSeparator => out.push_str(r"\,"),
+                PartKind::DecimalSeparator if part.text == "," => out.push_str("{,}"),
+                PartKind::Exponent => {
+                    let (times, exponent) = split_exponent(&part.text);
+                    let times = match times {
+                        "·" => r"\cdot",
+                        _ => r"\times",
+                    };
+                    write!(out, "{} 10^{{{}}}", times, exponent)
+                        .expect("writing to a String never fails");
+                }
+                _ => escape_latex(&part.text, out),
+            },
+            Self::Markdown => escape_markdown(&part.text, out),
+        }
+    }
+}
+
+fn escape_html(text: &str, out: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+fn escape_latex(text: &str, out: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '~' => out.push_str(r"\sim{}"),
+            '^' => out.push_str(r"\hat{}"),
+            '\\' => out.push_str(r"\backslash{}"),
+            // math mode ignores plain spaces
+            ' ' => out.push_str(r"\ "),
+            _ => out.push(ch),
+        }
+    }
+}
+
+fn escape_markdown(text: &str, out: &mut String) {
+    for ch in text.chars() {
+        if "\\`*_{}[]<>()#!|~".contains(ch) {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+}
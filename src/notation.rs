@@ -0,0 +1,132 @@
+use crate::buffer::StackStr;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// How the magnitude of a number is written.
+///
+/// - `Positional`: every digit is written in its place, i.e. `0.000123` (default)
+/// - `Scientific`: a single digit before the decimal, multiplied by a power of ten,
+///   i.e. `1.23e-4`. See [`ExponentStyle`] for how the power of ten is written.
+///
+/// Scientific notation is only compatible with [`Base::Decimal`][crate::Base::Decimal].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Notation {
+    Positional,
+    Scientific,
+}
+
+impl Default for Notation {
+    #[inline]
+    fn default() -> Self {
+        Self::Positional
+    }
+}
+
+/// How the exponent of a number in scientific notation is written.
+///
+/// - `LowerE`: `1.23e-4` (default)
+/// - `UpperE`: `1.23E-4`
+/// - `Superscript`: `1.23×10⁻⁴`, using Unicode superscript digits and sign
+/// - `Caret`: `1.23×10^-4`
+/// - `DotCaret`: `1.23·10^-4`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExponentStyle {
+    LowerE,
+    UpperE,
+    Superscript,
+    Caret,
+    DotCaret,
+}
+
+impl Default for ExponentStyle {
+    #[inline]
+    fn default() -> Self {
+        Self::LowerE
+    }
+}
+
+impl ExponentStyle {
+    /// Render the power of ten `exponent` in this style.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::ExponentStyle;
+    /// assert_eq!(ExponentStyle::LowerE.render(-4), "e-4");
+    /// assert_eq!(ExponentStyle::Superscript.render(-4), "×10⁻⁴");
+    /// assert_eq!(ExponentStyle::DotCaret.render(12), "·10^12");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn render(self, exponent: i32) -> String {
+        let mut rendered = String::new();
+        self.write(exponent, &mut rendered)
+            .expect("writing to a String never fails");
+        rendered
+    }
+
+    /// Write the power of ten `exponent` in this style.
+    pub(crate) fn write(self, exponent: i32, out: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            Self::LowerE => write!(out, "e{}", exponent),
+            Self::UpperE => write!(out, "E{}", exponent),
+            Self::Superscript => {
+                let mut digits = StackStr::<11>::default();
+                write!(digits, "{}", exponent)?;
+                out.write_str("×10")?;
+                digits
+                    .as_str()
+                    .chars()
+                    .try_for_each(|ch| out.write_char(superscript(ch)))
+            }
+            Self::Caret => write!(out, "×10^{}", exponent),
+            Self::DotCaret => write!(out, "·10^{}", exponent),
+        }
+    }
+}
+
+/// Split an exponent written in any [`ExponentStyle`] into its multiplication sign and power of
+/// ten, i.e. `×10⁻⁴` into `("×", -4)`. An `e` or `E` multiplies like `×`.
+#[cfg(feature = "alloc")]
+pub(crate) fn split_exponent(text: &str) -> (&'static str, i32) {
+    let (times, power) = if let Some(power) = text.strip_prefix("·10^") {
+        ("·", power)
+    } else if let Some(power) = text.strip_prefix("×10") {
+        ("×", power.strip_prefix('^').unwrap_or(power))
+    } else {
+        ("×", text.get(1..).unwrap_or_default())
+    };
+    let magnitude = power
+        .chars()
+        .filter_map(|ch| ch.to_digit(10).or_else(|| from_superscript(ch)))
+        .fold(0, |magnitude, digit| magnitude * 10 + digit as i32);
+    match power.chars().next() {
+        Some('-' | '⁻') => (times, -magnitude),
+        _ => (times, magnitude),
+    }
+}
+
+/// Digit of a superscript digit.
+#[cfg(feature = "alloc")]
+fn from_superscript(ch: char) -> Option<u32> {
+    "⁰¹²³⁴⁵⁶⁷⁸⁹"
+        .chars()
+        .position(|digit| digit == ch)
+        .map(|digit| digit as u32)
+}
+
+/// Superscript form of a digit or minus sign.
+fn superscript(ch: char) -> char {
+    match ch {
+        '-' => '⁻',
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        _ => unreachable!("integers render only digits and minus signs"),
+    }
+}
@@ -1,29 +1,393 @@
-use crate::{parse, Align, Base, Builder, Dynamic, Numeric, Sign};
-use iterext::prelude::*;
-use std::{any::type_name, collections::VecDeque, str::FromStr};
+#[cfg(feature = "std")]
+use crate::part::IoAdapter;
+use crate::part::{Discard, FmtSink, Measured, Sink};
+use crate::{
+    buffer::{Buffer, Grouped, StackStr, SEPARATOR},
+    parse,
+    text::Text,
+    Align, Base, BaseMarker, Builder, Currency, CurrencyDisplay, DisplayNum, Dynamic,
+    ExponentStyle, FormatString, Notation, Numeric, Overflow, PartKind, Plan, Ratio, Sign,
+    StdFlags, SymbolPosition, Unrepresentable, WidthUnit, ZeroSign,
+};
+#[cfg(feature = "alloc")]
+use crate::{AnsiStyle, Markup, Part};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+use core::{any::type_name, fmt};
+#[cfg(feature = "std")]
+use std::io;
 
-#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Error {
-    #[error("Zero formatter is only compatible with Align::Right or Align::Decimal")]
     IncompatibleAlignment,
-    #[error("{0:?} formatting not implemented for {1}")]
     NotImplemented(Base, &'static str),
+    IncompatibleScale,
+    /// The scale, including that implied by the ratio, is more than 400 powers of ten.
+    ///
+    /// No number has significant digits so far from the decimal point.
+    ScaleOutOfRange,
+    IncompatibleNotation,
+    Overflow(usize, usize),
+    Write,
+    /// The digits of the number exceed the fixed capacity of the formatting buffers.
+    ///
+    /// Only occurs without the `alloc` feature, for a [`Numeric`] implementation producing more
+    /// than 128 digits between its first and last nonzero digit.
+    Capacity,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IncompatibleAlignment => f.write_str(
+                "Zero formatter is only compatible with Align::Right, Align::Decimal, or Align::AfterSign",
+            ),
+            Self::NotImplemented(base, type_name) => {
+                write!(f, "{:?} formatting not implemented for {}", base, type_name)
+            }
+            Self::IncompatibleScale => f.write_str("Scaling is only compatible with Base::Decimal"),
+            Self::ScaleOutOfRange => write!(f, "scale must be within ±{}", MAX_SCALE),
+            Self::IncompatibleNotation => {
+                f.write_str("Scientific notation is only compatible with Base::Decimal")
+            }
+            Self::Overflow(width, max_width) => write!(
+                f,
+                "rendered width {} exceeds the maximum width {}",
+                width, max_width
+            ),
+            Self::Write => f.write_str("failed to write the formatted number"),
+            Self::Capacity => f.write_str("rendered digits exceed the capacity of the buffer"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Capacity of the rendered exponent of a number in scientific notation.
+const EXPONENT_CAPACITY: usize = 48;
+
+/// Largest magnitude of the total scale: enough to move the decimal past every digit of any
+/// `f64` or `u128`.
+const MAX_SCALE: i32 = 400;
+
+/// Sign printed after the number.
+#[derive(Clone, Copy, Debug)]
+enum SignSuffix {
+    Char(char),
+    Str(&'static str),
+}
+
+impl SignSuffix {
+    /// Length in bytes.
+    fn len(self) -> usize {
+        match self {
+            Self::Char(ch) => ch.len_utf8(),
+            Self::Str(s) => s.len(),
+        }
+    }
+
+    fn push_to(self, rendered: &mut impl Sink) {
+        match self {
+            Self::Char(ch) => rendered.push(PartKind::Sign, ch),
+            Self::Str(s) => rendered.push_str(PartKind::Sign, s),
+        }
+    }
+}
+
+/// Sign printed before the number and sign printed after the number.
+type SignAffixes = (Option<char>, Option<SignSuffix>);
+
+/// Base specification printed before and after the number.
+#[derive(Clone, Copy, Debug)]
+enum BaseAffixes {
+    Static(Option<&'static str>, Option<&'static str>),
+    /// The affixes of a [`BaseMarker::Custom`], borrowed from the configuration when rendering.
+    Custom,
+}
+
+impl BaseAffixes {
+    fn get(self, fmt: &NumFmt) -> (Option<&str>, Option<&str>) {
+        match self {
+            Self::Static(prefix, suffix) => (prefix, suffix),
+            Self::Custom => {
+                let (prefix, suffix) = fmt.base_marker().affixes(fmt.base());
+                (non_empty(prefix), non_empty(suffix))
+            }
+        }
+    }
+}
+
+fn non_empty(affix: &str) -> Option<&str> {
+    Some(affix).filter(|affix| !affix.is_empty())
+}
+
+/// Settings of a [`NumFmt`] resolved for particular dynamic parameters.
+///
+/// Everything which does not depend on the number being formatted is derived once, here.
+#[derive(Clone, Debug)]
+pub(crate) struct Resolved {
+    /// Width available for the digits once the affixes are accounted for, not including an
+    /// exponent.
+    digits_width: usize,
+    /// Whether an exponent counts against the width; it does not with `Align::Decimal`.
+    exponent_counts: bool,
+    separator: Option<(char, usize)>,
+    separator_width: usize,
+    decimal_separator_width: usize,
+    min_fraction_digits: usize,
+    max_fraction_digits: Option<usize>,
+    /// Total power of ten by which the number is multiplied.
+    scale: i32,
+    /// Currency marker preceding the number, and whether a space separates them.
+    currency_prefix: Option<(&'static str, bool)>,
+    /// Currency marker following the number, and whether a space separates them.
+    currency_suffix: Option<(&'static str, bool)>,
+    /// Whether the sign precedes a currency marker preceding the number.
+    sign_before_currency: bool,
+    base_affixes: BaseAffixes,
+    /// Signs of positive and negative numbers, indexed by whether the number is negative.
+    signs: [SignAffixes; 2],
+    /// Signs of numbers which render as zero, according to the zero sign policy, indexed by
+    /// whether the number is negative.
+    zero_signs: [SignAffixes; 2],
+    /// Length in bytes of the affixes, not including an exponent or a sign suffix.
+    affix_len: usize,
+}
+
+impl Resolved {
+    /// Resolve the settings of `fmt` for the dynamic parameters.
+    ///
+    /// Fails if the settings are incompatible with each other.
+    pub(crate) fn new(fmt: &NumFmt, dynamic: Dynamic) -> Result<Resolved, Error> {
+        if fmt.zero() && matches!(fmt.align(), Align::Left | Align::Center) {
+            return Err(Error::IncompatibleAlignment);
+        }
+        let scale = match fmt.scale_exponent() {
+            Some(scale) if (-MAX_SCALE..=MAX_SCALE).contains(&scale) => scale,
+            _ => return Err(Error::ScaleOutOfRange),
+        };
+        if scale != 0 && fmt.base() != Base::Decimal {
+            return Err(Error::IncompatibleScale);
+        }
+        if fmt.notation() == Notation::Scientific && fmt.base() != Base::Decimal {
+            return Err(Error::IncompatibleNotation);
+        }
+
+        let (currency_prefix, currency_suffix) = match fmt.currency_marker() {
+            Some((SymbolPosition::Prefix, marker)) => (Some(marker), None),
+            Some((SymbolPosition::Suffix, marker)) => (None, Some(marker)),
+            None => (None, None),
+        };
+        let base_affixes = fmt.base_affixes();
+        let (base_prefix, base_suffix) = base_affixes.get(fmt);
+        let currency_marker =
+            |currency: Option<(&'static str, bool)>| currency.map(|(marker, _)| marker);
+        // a space separating the number from the currency marker
+        let currency_space = |currency: Option<(&'static str, bool)>| {
+            currency
+                .map(|(_, spaced)| spaced as usize)
+                .unwrap_or_default()
+        };
+        let prefixes = [fmt.prefix(), currency_marker(currency_prefix), base_prefix];
+        let suffixes = [
+            base_suffix,
+            fmt.ratio().map(Ratio::suffix),
+            currency_marker(currency_suffix),
+            fmt.suffix(),
+        ];
+        let unit = fmt.width_unit();
+        let prefix_width = prefixes
+            .iter()
+            .flatten()
+            .map(|affix| unit.str_width(affix))
+            .sum::<usize>()
+            + currency_space(currency_prefix);
+        let signs = [fmt.sign_affixes(false), fmt.sign_affixes(true)];
+        let zero_signs = [false, true].map(|negative| match fmt.zero_sign() {
+            ZeroSign::Natural | ZeroSign::Preserve => fmt.sign_affixes(negative),
+            ZeroSign::Positive => fmt.sign_affixes(false),
+            ZeroSign::Unsigned => (None, fmt.sign_affixes(false).1),
+            ZeroSign::PlusMinus => (Some('±'), fmt.sign_affixes(false).1),
+        });
+        let sign_suffix_width = signs[0]
+            .1
+            .map(|suffix| match suffix {
+                SignSuffix::Char(ch) => unit.char_width(ch),
+                SignSuffix::Str(s) => unit.str_width(s),
+            })
+            .unwrap_or_default();
+        let suffix_width = suffixes
+            .iter()
+            .flatten()
+            .map(|affix| unit.str_width(affix))
+            .sum::<usize>()
+            + currency_space(currency_suffix)
+            + sign_suffix_width;
+        let affix_len = prefixes
+            .iter()
+            .chain(&suffixes)
+            .flatten()
+            .map(|affix| affix.len())
+            .sum::<usize>()
+            + currency_space(currency_prefix)
+            + currency_space(currency_suffix);
+        let (min_fraction_digits, max_fraction_digits) = fmt.fraction_digits_with(dynamic);
+
+        // With `Align::Decimal`, the width only covers the portion before the decimal, so
+        // suffixes do not count against it.
+        let exponent_counts = fmt.align() != Align::Decimal;
+        let mut digits_width = fmt.width_with(dynamic).saturating_sub(prefix_width);
+        if exponent_counts {
+            digits_width = digits_width.saturating_sub(suffix_width);
+        }
+        let separator = fmt.separator_and_spacing_with(dynamic);
+
+        Ok(Resolved {
+            digits_width,
+            exponent_counts,
+            separator,
+            separator_width: separator
+                .map(|(separator, _)| unit.char_width(separator))
+                .unwrap_or_default(),
+            decimal_separator_width: unit.char_width(fmt.decimal_separator()),
+            min_fraction_digits,
+            max_fraction_digits,
+            scale,
+            currency_prefix,
+            currency_suffix,
+            sign_before_currency: fmt
+                .currency()
+                .map(|currency| currency.sign_before_symbol)
+                .unwrap_or(true),
+            base_affixes,
+            signs,
+            zero_signs,
+            affix_len,
+        })
+    }
+
+    /// Width available for the digits of the number, once the affixes are accounted for.
+    ///
+    /// `exponent_width` is the width of the exponent of a number rendered in scientific
+    /// notation.
+    #[inline]
+    fn width_desired(&self, exponent_width: usize) -> usize {
+        let exponent_width = if self.exponent_counts {
+            exponent_width
+        } else {
+            0
+        };
+        self.digits_width.saturating_sub(exponent_width).max(1)
+    }
+
+    /// Override the count of digits after the decimal.
+    fn set_precision(&mut self, precision: usize) {
+        self.min_fraction_digits = precision;
+        self.max_fraction_digits = Some(precision);
+    }
+}
+
+/// Shift the decimal point of a number by `exponent` places; i.e. multiply it by `10**exponent`.
+///
+/// The inputs and outputs follow the conventions of [`Numeric::decimal`]: the left digits iterate
+/// from least to most significant, and the right digits iterate away from the decimal.
+fn shift_decimal(
+    left: impl Iterator<Item = char>,
+    right: Option<impl Iterator<Item = char>>,
+    exponent: i32,
+) -> Result<(Buffer, Option<Buffer>), Error> {
+    let mut left = Buffer::from_digits(left)?;
+    let mut right = match right {
+        Some(right) => Buffer::from_digits(right)?,
+        None => Buffer::new(),
+    };
+    let places = exponent.unsigned_abs() as usize;
+
+    let (from, onto) = if exponent > 0 {
+        (&mut right, &mut left)
+    } else {
+        (&mut left, &mut right)
+    };
+    from.pad_zeros(places);
+    let mut shifted = Buffer::new();
+    for digit in from.iter().take(places).rev().chain(onto.iter()) {
+        shifted.push(digit)?;
+    }
+    let mut remainder = Buffer::new();
+    for digit in from.iter().skip(places) {
+        remainder.push(digit)?;
+    }
+    *onto = shifted;
+    *from = remainder;
+
+    // shifting can expose leading zeros on the left and trailing zeros on the right
+    while left.last() == Some(b'0') {
+        left.pop();
+    }
+    while right.last() == Some(b'0') {
+        right.pop();
+    }
+
+    let right = if right.is_empty() { None } else { Some(right) };
+    Ok((left, right))
+}
+
+/// Exponent of the most significant digit of a number, as returned by [`shift_decimal`].
+///
+/// Zero has an exponent of 0.
+fn scientific_exponent(left: &Buffer, right: Option<&Buffer>) -> i32 {
+    if !left.is_empty() {
+        return left.len() as i32 - 1;
+    }
+    right
+        .and_then(|right| right.iter().position(|digit| digit != b'0'))
+        .map(|idx| -(idx as i32) - 1)
+        .unwrap_or_default()
+}
+
+/// Iterate over the digits of a [`Buffer`] as `char`s.
+fn chars(digits: &Buffer) -> impl Iterator<Item = char> + '_ {
+    digits.iter().map(char::from)
 }
 
 /// Formatter for numbers.
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 pub struct NumFmt {
-    pub(crate) fill: Option<char>,
+    pub(crate) fill: Option<Text>,
+    pub(crate) right_fill: Option<Text>,
     pub(crate) align: Align,
     pub(crate) sign: Sign,
+    pub(crate) zero_sign: ZeroSign,
+    pub(crate) minus_sign: Option<char>,
+    pub(crate) plus_sign: Option<char>,
     pub(crate) hash: bool,
+    pub(crate) base_marker: BaseMarker,
     pub(crate) zero: bool,
     pub(crate) width: usize,
+    pub(crate) width_unit: WidthUnit,
+    pub(crate) max_width: Option<usize>,
+    pub(crate) overflow: Overflow,
+    pub(crate) min_integer_digits: Option<usize>,
+    pub(crate) max_integer_digits: Option<usize>,
     pub(crate) precision: Option<usize>,
+    pub(crate) min_fraction_digits: Option<usize>,
+    pub(crate) max_fraction_digits: Option<usize>,
+    pub(crate) force_decimal_point: bool,
     pub(crate) base: Base,
+    pub(crate) notation: Notation,
+    pub(crate) exponent_style: ExponentStyle,
     pub(crate) separator: Option<char>,
     pub(crate) spacing: Option<usize>,
     pub(crate) decimal_separator: Option<char>,
+    pub(crate) ratio: Option<Ratio>,
+    pub(crate) scale: i32,
+    pub(crate) currency: Option<Currency>,
+    pub(crate) currency_display: CurrencyDisplay,
+    pub(crate) prefix: Option<Text>,
+    pub(crate) suffix: Option<Text>,
 }
 
 impl NumFmt {
@@ -34,51 +398,159 @@ impl NumFmt {
 
     /// Parse a `NumFmt` instance from a format string.
     ///
-    /// See crate-level documentation for the grammar.
-    pub fn from_str(s: &str) -> Result<Self, parse::Error> {
+    /// See crate-level documentation for the grammar. Parsing is also available in `const`
+    /// contexts, so a format string can be validated at compile time:
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{parse, NumFmt};
+    /// static PRICE: Result<NumFmt, parse::Error> = NumFmt::from_str(">10.2,");
+    /// const _: () = assert!(PRICE.is_ok());
+    ///
+    /// let price = PRICE.as_ref().unwrap();
+    /// assert_eq!(price.fmt(1234.5).unwrap(), "  1,234.50");
+    ///
+    /// let err = NumFmt::from_str(">10.2f").unwrap_err();
+    /// assert_eq!(err.span(), 5..6);
+    /// assert_eq!(err.kind(), parse::ErrorKind::UnknownFormat('f'));
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub const fn from_str(s: &str) -> Result<Self, parse::Error<'_>> {
         parse::parse(s)
     }
 
-    #[inline]
-    fn width_desired(&self, dynamic: Dynamic) -> usize {
-        let mut width_desired = self.width_with(dynamic);
-        if self.hash() {
-            width_desired = width_desired.saturating_sub(2);
+    /// The canonical format string of this configuration.
+    ///
+    /// [`NumFmt::from_str`] parses the displayed string back into an equal `NumFmt`. Settings
+    /// only available through the [`Builder`], such as a decimal separator or a custom group
+    /// separator, have no format string syntax and produce an [`Unrepresentable`] error.
+    ///
+    /// The returned [`FormatString`] implements `Display`. `NumFmt` itself does not, since not
+    /// every configuration has a format string.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{NumFmt, Unrepresentable};
+    /// let fmt = NumFmt::from_str("*^+#012.3x_4").unwrap();
+    /// let format_string = fmt.format_string().unwrap().to_string();
+    /// assert_eq!(format_string, "^+#012.3x_4");
+    /// assert_eq!(NumFmt::from_str(&format_string).unwrap(), fmt);
+    ///
+    /// let fmt = NumFmt::builder().separator(Some('.')).decimal_separator(',').build();
+    /// assert_eq!(fmt.format_string().unwrap_err(), Unrepresentable::Separator('.'));
+    /// ```
+    pub fn format_string(&self) -> Result<FormatString<'_>, Unrepresentable> {
+        FormatString::new(self)
+    }
+
+    /// normalize a digit iterator
+    ///
+    /// - keep only the low-order digits permitted by the maximum integer digits
+    /// - ensure that the buffer holds, bare minimum, a single digit (default 0)
+    /// - pad it to the minimum integer digits with zeros
+    /// - pad it to the desired width, for the zero handler
+    /// - group it by the desired spacing
+    fn normalize(
+        &self,
+        digits: impl Iterator<Item = char>,
+        resolved: &Resolved,
+        width_desired: usize,
+    ) -> Result<Grouped, Error> {
+        let mut digits =
+            Buffer::from_digits(digits.take(self.max_integer_digits().unwrap_or(usize::MAX)))?;
+        if digits.is_empty() {
+            digits.push(b'0')?;
         }
-        if width_desired == 0 {
-            width_desired = 1;
+        digits.pad_zeros(self.min_integer_digits().unwrap_or_default());
+        if self.zero() {
+            digits.pad_zeros(width_desired);
         }
 
-        width_desired
+        let spacing = resolved.separator.map(|(_, spacing)| spacing);
+        Ok(Grouped::new(digits, spacing))
     }
 
-    /// normalize a digit iterator
+    /// Normalize the digits of a decimal number, and collect its fraction.
     ///
-    /// - ensure that the iterator returns, bare minimum, a single char (default 0)
-    /// - pad it to the desired width
-    /// - space it out to the desired spacing
-    fn normalize(&self, digits: impl Iterator<Item = char>, dynamic: Dynamic) -> VecDeque<char> {
-        let pad_to = if self.zero() {
-            self.width_desired(dynamic)
+    /// Returns the digits before the decimal in the manner of
+    /// [`normalize`][NumFmt::normalize], and the digits after it, if the decimal is printed.
+    fn decimal_digits(
+        &self,
+        left: impl Iterator<Item = char>,
+        right: Option<impl Iterator<Item = char>>,
+        resolved: &Resolved,
+        width_desired: usize,
+    ) -> Result<(Grouped, Option<Buffer>), Error> {
+        let integer = self.normalize(left, resolved, width_desired)?;
+        let min_fraction_digits = resolved.min_fraction_digits;
+        let mut fraction = Buffer::from_digits(
+            right
+                .into_iter()
+                .flatten()
+                .take(resolved.max_fraction_digits.unwrap_or(usize::MAX)),
+        )?;
+        fraction.pad_zeros(min_fraction_digits);
+        while fraction.len() > min_fraction_digits && fraction.last() == Some(b'0') {
+            fraction.pop();
+        }
+        let fraction = if !fraction.is_empty() || self.force_decimal_point() {
+            Some(fraction)
         } else {
-            1
+            None
         };
+        Ok((integer, fraction))
+    }
 
-        let pad_char = if self.zero() { '0' } else { self.fill() };
-
-        let mut digits = digits.peekable();
-        let mut digits: Box<dyn Iterator<Item = char>> = if digits.peek().is_some() {
-            Box::new(digits)
-        } else {
-            Box::new(std::iter::once('0'))
-        };
+    /// Text standing in for the digits of a number which is not finite, least significant first.
+    ///
+    /// It is never scaled, separated or rendered in scientific notation.
+    fn non_finite(&self, text: &str, width_desired: usize) -> Result<Grouped, Error> {
+        let mut text = Buffer::from_digits(text.chars().rev())?;
+        if self.zero() {
+            text.pad_zeros(width_desired);
+        }
+        Ok(Grouped::new(text, None))
+    }
 
-        digits = Box::new(digits.pad(pad_char, pad_to));
+    /// Push `count` units of padding from a repeating `pattern`.
+    ///
+    /// The pattern is anchored to the start of the rendered output, so that the padding of
+    /// numbers formatted to the same width lines up in columns. When a wide char of the pattern
+    /// does not fit in the remaining space, the remainder is padded with spaces.
+    fn push_padding<S: Sink>(&self, rendered: &mut Measured<'_, S>, pattern: &str, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let unit = self.width_unit();
+        let pattern_width = unit.str_width(pattern);
+        let pattern = if pattern_width == 0 { " " } else { pattern };
+        let pattern_width = pattern_width.max(1);
 
-        if let Some((separator, spacing)) = self.separator_and_spacing_with(dynamic) {
-            digits.separate(separator, spacing)
-        } else {
-            digits.collect()
+        let mut skip = rendered.width() % pattern_width;
+        let mut remaining = count;
+        for ch in pattern.chars().cycle() {
+            let ch_width = unit.char_width(ch);
+            if skip > 0 {
+                if ch_width > skip {
+                    // the anchor falls within a wide char; pad out its remainder
+                    let partial = (ch_width - skip).min(remaining);
+                    for _ in 0..partial {
+                        rendered.push(PartKind::Padding, ' ');
+                    }
+                    remaining -= partial;
+                }
+                skip = skip.saturating_sub(ch_width);
+                continue;
+            }
+            if remaining == 0 {
+                break;
+            }
+            if ch_width > remaining {
+                for _ in 0..remaining {
+                    rendered.push(PartKind::Padding, ' ');
+                }
+                break;
+            }
+            rendered.push(PartKind::Padding, ch);
+            remaining -= ch_width;
         }
     }
 
@@ -87,6 +559,7 @@ impl NumFmt {
     /// Will return `None` in the event that the configured format is incompatible with
     /// the number provided. This is most often the case when the number is not an
     /// integer but an integer format such as `b`, `o`, or `x` is configured.
+    #[cfg(feature = "alloc")]
     pub fn fmt<N: Numeric>(&self, number: N) -> Result<String, Error> {
         self.fmt_with(number, Dynamic::default())
     }
@@ -105,138 +578,513 @@ impl NumFmt {
     /// Will return `None` in the event that the configured format is incompatible with
     /// the number provided. This is most often the case when the number is not an
     /// integer but an integer format such as `b`, `o`, or `x` is configured.
+    #[cfg(feature = "alloc")]
     pub fn fmt_with<N: Numeric>(&self, number: N, dynamic: Dynamic) -> Result<String, Error> {
-        if self.zero() && !(self.align() == Align::Right || self.align() == Align::Decimal) {
-            return Err(Error::IncompatibleAlignment);
+        let mut rendered = String::new();
+        self.fmt_into(&number, dynamic, &mut rendered)?;
+        Ok(rendered)
+    }
+
+    /// Format the provided number according to this configuration, as a sequence of typed
+    /// spans.
+    ///
+    /// This is useful to style parts of the number differently, i.e. to dim the group
+    /// separators. Concatenating the text of the parts produces the same output as
+    /// [`fmt`][NumFmt::fmt]. Adjacent spans of the same [kind][PartKind] are merged into a
+    /// single part.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::{NumFmt, Part, PartKind};
+    /// let fmt = NumFmt::from_str("+,").unwrap();
+    /// assert_eq!(
+    ///     fmt.fmt_to_parts(1234.5).unwrap(),
+    ///     vec![
+    ///         Part::new(PartKind::Sign, "+"),
+    ///         Part::new(PartKind::Integer, "1"),
+    ///         Part::new(PartKind::GroupSeparator, ","),
+    ///         Part::new(PartKind::Integer, "234"),
+    ///         Part::new(PartKind::DecimalSeparator, "."),
+    ///         Part::new(PartKind::Fraction, "5"),
+    ///     ],
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn fmt_to_parts<N: Numeric>(&self, number: N) -> Result<Vec<Part>, Error> {
+        self.fmt_to_parts_with(number, Dynamic::default())
+    }
+
+    /// Format the provided number according to this configuration and dynamic parameters,
+    /// as a sequence of typed spans.
+    ///
+    /// See [`fmt_to_parts`][NumFmt::fmt_to_parts].
+    #[cfg(feature = "alloc")]
+    pub fn fmt_to_parts_with<N: Numeric>(
+        &self,
+        number: N,
+        dynamic: Dynamic,
+    ) -> Result<Vec<Part>, Error> {
+        let mut parts = Vec::new();
+        self.fmt_into(&number, dynamic, &mut parts)?;
+        Ok(parts)
+    }
+
+    /// Adapt the provided number to be formatted according to this configuration wherever a
+    /// [`Display`][fmt::Display] implementation is accepted, i.e. by `format!` or `write!`.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::from_str("+.1").unwrap();
+    /// assert_eq!(format!("[{}]", fmt.display(2.25)), "[+2.2]");
+    /// ```
+    ///
+    /// See [`DisplayNum`] for the handling of errors.
+    pub fn display<N: Numeric>(&self, number: N) -> DisplayNum<'_, N> {
+        self.display_with(number, Dynamic::default())
+    }
+
+    /// Adapt the provided number to be formatted according to this configuration and dynamic
+    /// parameters wherever a [`Display`][fmt::Display] implementation is accepted.
+    ///
+    /// See [`display`][NumFmt::display].
+    pub fn display_with<N: Numeric>(&self, number: N, dynamic: Dynamic) -> DisplayNum<'_, N> {
+        DisplayNum::new(self, number, dynamic)
+    }
+
+    /// Wrap the provided number to be formatted according to this configuration, overridden by
+    /// the flags of the standard library's format string, i.e. `{:>12.2}` or `{:#x}`.
+    ///
+    /// See [`StdFlags`].
+    pub fn std_flags<N: Numeric>(&self, number: N) -> StdFlags<'_, N> {
+        StdFlags::with_fmt(self, number)
+    }
+
+    /// Format the provided number according to this configuration into a writer.
+    ///
+    /// The output is streamed directly to the writer, without building an intermediate
+    /// `String`. The digits are collected on the stack, so formatting a primitive number does
+    /// not allocate, and a single buffer can be reused to format many numbers:
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::from_str(">6.2").unwrap();
+    /// let mut buffer = String::new();
+    /// for number in [1.5, -20.0] {
+    ///     fmt.write_to(&mut buffer, number).unwrap();
+    ///     buffer.push(';');
+    /// }
+    /// assert_eq!(buffer, "  1.50;-20.00;");
+    /// ```
+    ///
+    /// If the writer fails, returns [`Error::Write`]. Output written before the failure, or
+    /// before a formatting error, remains in the writer.
+    pub fn write_to<W, N>(&self, writer: &mut W, number: N) -> Result<(), Error>
+    where
+        W: fmt::Write + ?Sized,
+        N: Numeric,
+    {
+        self.write_to_with(writer, number, Dynamic::default())
+    }
+
+    /// Format the provided number according to this configuration and dynamic parameters into
+    /// a writer.
+    ///
+    /// See [`write_to`][NumFmt::write_to].
+    pub fn write_to_with<W, N>(
+        &self,
+        writer: &mut W,
+        number: N,
+        dynamic: Dynamic,
+    ) -> Result<(), Error>
+    where
+        W: fmt::Write + ?Sized,
+        N: Numeric,
+    {
+        let mut sink = FmtSink::new(writer);
+        self.fmt_into(&number, dynamic, &mut sink)?;
+        sink.result.map_err(|_| Error::Write)
+    }
+
+    /// Format the provided number according to this configuration into an I/O stream.
+    ///
+    /// The output is streamed directly to the writer, without building an intermediate
+    /// `String`. It is written in many small pieces, so unbuffered writers such as a
+    /// [`File`][std::fs::File] should be wrapped in a [`BufWriter`][std::io::BufWriter].
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let fmt = NumFmt::from_str("#x").unwrap();
+    /// let mut buffer = Vec::new();
+    /// fmt.write_io(&mut buffer, 255).unwrap();
+    /// assert_eq!(buffer, b"0xff");
+    /// ```
+    ///
+    /// Formatting errors are reported as [`io::ErrorKind::InvalidInput`] wrapping an [`Error`].
+    #[cfg(feature = "std")]
+    pub fn write_io<W, N>(&self, writer: &mut W, number: N) -> io::Result<()>
+    where
+        W: io::Write + ?Sized,
+        N: Numeric,
+    {
+        self.write_io_with(writer, number, Dynamic::default())
+    }
+
+    /// Format the provided number according to this configuration and dynamic parameters into
+    /// an I/O stream.
+    ///
+    /// See [`write_io`][NumFmt::write_io].
+    #[cfg(feature = "std")]
+    pub fn write_io_with<W, N>(&self, writer: &mut W, number: N, dynamic: Dynamic) -> io::Result<()>
+    where
+        W: io::Write + ?Sized,
+        N: Numeric,
+    {
+        let mut adapter = IoAdapter::new(writer);
+        let result = self.write_to_with(&mut adapter, number, dynamic);
+        adapter.finish(result)
+    }
+
+    /// Compile this configuration and dynamic parameters into a [`Plan`] for formatting many
+    /// numbers of type `N`.
+    ///
+    /// Fails if the configuration is invalid, i.e. zero padding with left alignment.
+    pub fn compile<N: Numeric>(&self, dynamic: Dynamic) -> Result<Plan<N>, Error> {
+        Plan::new(self, dynamic)
+    }
+
+    /// Format the provided number according to this configuration, styled with ANSI escape
+    /// codes for display in a terminal.
+    ///
+    /// See [`AnsiStyle`].
+    #[cfg(feature = "alloc")]
+    pub fn fmt_ansi<N: Numeric>(&self, number: N, style: &AnsiStyle) -> Result<String, Error> {
+        self.fmt_ansi_with(number, Dynamic::default(), style)
+    }
+
+    /// Format the provided number according to this configuration and dynamic parameters,
+    /// styled with ANSI escape codes for display in a terminal.
+    ///
+    /// See [`AnsiStyle`].
+    #[cfg(feature = "alloc")]
+    pub fn fmt_ansi_with<N: Numeric>(
+        &self,
+        number: N,
+        dynamic: Dynamic,
+        style: &AnsiStyle,
+    ) -> Result<String, Error> {
+        let negative = number.is_negative() && self.base() == Base::Decimal;
+        let mut parts = Vec::new();
+        self.fmt_into(&number, dynamic, &mut parts)?;
+        let mut styled = String::new();
+        for part in &parts {
+            style.paint(part, negative, &mut styled);
         }
+        Ok(styled)
+    }
+
+    /// Format the provided number according to this configuration, marked up for the target
+    /// language.
+    ///
+    /// See [`Markup`].
+    #[cfg(feature = "alloc")]
+    pub fn fmt_markup<N: Numeric>(&self, number: N, markup: Markup) -> Result<String, Error> {
+        self.fmt_markup_with(number, Dynamic::default(), markup)
+    }
+
+    /// Format the provided number according to this configuration and dynamic parameters,
+    /// marked up for the target language.
+    ///
+    /// See [`Markup`].
+    #[cfg(feature = "alloc")]
+    pub fn fmt_markup_with<N: Numeric>(
+        &self,
+        number: N,
+        dynamic: Dynamic,
+        markup: Markup,
+    ) -> Result<String, Error> {
+        let mut parts = Vec::new();
+        self.fmt_into(&number, dynamic, &mut parts)?;
+        let mut marked_up = String::new();
+        for part in &parts {
+            markup.push_part(part, &mut marked_up);
+        }
+        Ok(marked_up)
+    }
+
+    /// Render the number into `sink`, applying the maximum width.
+    pub(crate) fn fmt_into<N: Numeric, S: Sink>(
+        &self,
+        number: &N,
+        dynamic: Dynamic,
+        sink: &mut S,
+    ) -> Result<(), Error> {
+        self.fmt_resolved(&Resolved::new(self, dynamic)?, number, sink)
+    }
+
+    /// Render the number into `sink` according to settings previously resolved from this
+    /// configuration, applying the maximum width.
+    pub(crate) fn fmt_resolved<N: Numeric, S: Sink>(
+        &self,
+        resolved: &Resolved,
+        number: &N,
+        sink: &mut S,
+    ) -> Result<(), Error> {
+        let max_width = match self.max_width() {
+            Some(max_width) => max_width,
+            None => return self.render(resolved, number, false, sink, None).map(drop),
+        };
+        // measure before committing anything to the sink
+        let width = self.render(resolved, number, false, &mut Discard, None)?;
+        if width <= max_width {
+            return self.render(resolved, number, false, sink, None).map(drop);
+        }
+
+        let fill = |sink: &mut S| {
+            for _ in 0..max_width {
+                sink.push(PartKind::Overflow, '#');
+            }
+            Ok(())
+        };
+        match self.overflow() {
+            Overflow::Extend => self.render(resolved, number, false, sink, None).map(drop),
+            Overflow::Error => Err(Error::Overflow(width, max_width)),
+            Overflow::Fill => fill(sink),
+            Overflow::Truncate => {
+                if max_width > 0 {
+                    self.render(resolved, number, false, sink, Some(max_width - 1))?;
+                    sink.push(PartKind::Overflow, '…');
+                }
+                Ok(())
+            }
+            Overflow::Scientific => {
+                if self.base() != Base::Decimal {
+                    return fill(sink);
+                }
+                // the mantissa never needs more fractional digits than there are columns
+                let mut resolved = resolved.clone();
+                for precision in (0..max_width).rev() {
+                    resolved.set_precision(precision);
+                    if self.render(&resolved, number, true, &mut Discard, None)? <= max_width {
+                        return self.render(&resolved, number, true, sink, None).map(drop);
+                    }
+                }
+                fill(sink)
+            }
+        }
+    }
+
+    /// Render the number into `sink`, without regard for the maximum width, and return the
+    /// rendered width.
+    ///
+    /// If `scientific` is set, the number is rendered in scientific notation regardless of the
+    /// configured [`notation`][NumFmt::notation]. If a `limit` is set, the output is truncated
+    /// to that width.
+    fn render<N: Numeric>(
+        &self,
+        resolved: &Resolved,
+        number: &N,
+        scientific: bool,
+        sink: &mut impl Sink,
+        limit: Option<usize>,
+    ) -> Result<usize, Error> {
+        let scale = resolved.scale;
+        let scientific = scientific || self.notation() == Notation::Scientific;
         let negative = number.is_negative() && self.base() == Base::Decimal;
         let decimal_separator = self.decimal_separator();
+        let not_implemented = || Error::NotImplemented(self.base(), type_name::<N>());
 
-        // if the separator is set, returns true when it matches the provided char
-        // otherwise, always false
-        let matches_separator = |ch: char| {
-            self.separator_and_spacing_with(dynamic)
-                .map(|(separator, _)| separator == ch)
-                .unwrap_or_default()
-        };
+        // exponent of a number rendered in scientific notation, i.e. `e-4`
+        let mut exponent: Option<StackStr<EXPONENT_CAPACITY>> = None;
 
-        // core formatting: construct a reversed queue of digits, with separator and decimal
-        // decimal is the index of the decimal point
-        let (mut digits, decimal_pos): (VecDeque<_>, Option<usize>) = match self.base() {
+        // core formatting: construct the digits before the decimal, least significant first and
+        // marked with separators, and the digits after it, if the decimal is printed
+        let (mut integer, fraction) = match self.base() {
             Base::Binary => (
                 self.normalize(
-                    number
-                        .binary()
-                        .ok_or_else(|| Error::NotImplemented(self.base(), type_name::<N>()))?,
-                    dynamic,
-                ),
+                    number.binary().ok_or_else(not_implemented)?,
+                    resolved,
+                    resolved.width_desired(0),
+                )?,
                 None,
             ),
             Base::Octal => (
                 self.normalize(
-                    number
-                        .octal()
-                        .ok_or_else(|| Error::NotImplemented(self.base(), type_name::<N>()))?,
-                    dynamic,
-                ),
+                    number.octal().ok_or_else(not_implemented)?,
+                    resolved,
+                    resolved.width_desired(0),
+                )?,
                 None,
             ),
-            Base::Decimal => {
-                let (left, right) = number.decimal();
-                let mut dq = self.normalize(left, dynamic);
-                let decimal = dq.len();
-                let past_decimal: Option<Box<dyn Iterator<Item = char>>> =
-                    match (right, self.precision_with(dynamic)) {
-                        (Some(digits), None) => Some(Box::new(digits)),
-                        (Some(digits), Some(precision)) => Some(Box::new(
-                            digits.chain(std::iter::repeat('0')).take(precision),
-                        )),
-                        (None, Some(precision)) => {
-                            Some(Box::new(std::iter::repeat('0').take(precision)))
-                        }
-                        (None, None) => None,
-                    };
-                if let Some(past_decimal) = past_decimal {
-                    dq.push_front(self.decimal_separator());
-
-                    // .extend only pushes to the back
-                    for item in past_decimal {
-                        dq.push_front(item);
-                    }
-                }
-                (dq, Some(decimal))
+            Base::Decimal if number.is_nan() => {
+                (self.non_finite("NaN", resolved.width_desired(0))?, None)
             }
+            Base::Decimal if number.is_infinite() => {
+                (self.non_finite("inf", resolved.width_desired(0))?, None)
+            }
+            Base::Decimal => match number.decimal() {
+                (left, right) if scientific => {
+                    let (left, right) = shift_decimal(left, right, scale)?;
+                    let shift = scientific_exponent(&left, right.as_ref());
+                    let mut rendered = StackStr::default();
+                    self.exponent_style()
+                        .write(shift, &mut rendered)
+                        .expect("exponent fits in its buffer");
+                    let width_desired =
+                        resolved.width_desired(self.width_unit().str_width(rendered.as_str()));
+                    exponent = Some(rendered);
+                    let (left, right) =
+                        shift_decimal(chars(&left), right.as_ref().map(chars), -shift)?;
+                    self.decimal_digits(
+                        chars(&left),
+                        right.as_ref().map(chars),
+                        resolved,
+                        width_desired,
+                    )?
+                }
+                (left, right) if scale == 0 => {
+                    self.decimal_digits(left, right, resolved, resolved.width_desired(0))?
+                }
+                (left, right) => {
+                    let (left, right) = shift_decimal(left, right, scale)?;
+                    self.decimal_digits(
+                        chars(&left),
+                        right.as_ref().map(chars),
+                        resolved,
+                        resolved.width_desired(0),
+                    )?
+                }
+            },
             Base::LowerHex => (
                 self.normalize(
-                    number
-                        .hex()
-                        .ok_or_else(|| Error::NotImplemented(self.base(), type_name::<N>()))?,
-                    dynamic,
-                ),
+                    number.hex().ok_or_else(not_implemented)?,
+                    resolved,
+                    resolved.width_desired(0),
+                )?,
                 None,
             ),
             Base::UpperHex => (
                 self.normalize(
                     number
                         .hex()
-                        .ok_or_else(|| Error::NotImplemented(self.base(), type_name::<N>()))?
+                        .ok_or_else(not_implemented)?
                         .map(|ch| ch.to_ascii_uppercase()),
-                    dynamic,
-                ),
+                    resolved,
+                    resolved.width_desired(0),
+                )?,
                 None,
             ),
         };
 
         debug_assert!(
-            {
-                let legal: Box<dyn Fn(&char) -> bool> = match self.base() {
-                    Base::Binary => {
-                        Box::new(move |ch| matches_separator(*ch) || ('0'..='1').contains(ch))
-                    }
-                    Base::Octal => {
-                        Box::new(move |ch| matches_separator(*ch) || ('0'..='7').contains(ch))
-                    }
-                    Base::Decimal => Box::new(move |ch| {
-                        *ch == decimal_separator
-                            || matches_separator(*ch)
-                            || ('0'..='9').contains(ch)
-                    }),
-                    Base::LowerHex => Box::new(move |ch| {
-                        matches_separator(*ch)
-                            || ('0'..='9').contains(ch)
-                            || ('a'..='f').contains(ch)
-                    }),
-                    Base::UpperHex => Box::new(move |ch| {
-                        matches_separator(*ch)
-                            || ('0'..='9').contains(ch)
-                            || ('A'..='F').contains(ch)
-                    }),
+            number.is_nan() || number.is_infinite() || {
+                let legal = |digit: u8| match self.base() {
+                    Base::Binary => (b'0'..=b'1').contains(&digit),
+                    Base::Octal => (b'0'..=b'7').contains(&digit),
+                    Base::Decimal => digit.is_ascii_digit(),
+                    Base::LowerHex => digit.is_ascii_digit() || (b'a'..=b'f').contains(&digit),
+                    Base::UpperHex => digit.is_ascii_digit() || (b'A'..=b'F').contains(&digit),
                 };
-                digits.iter().all(legal)
+                integer
+                    .iter()
+                    .all(|digit| digit == SEPARATOR || legal(digit))
+                    && fraction
+                        .iter()
+                        .flat_map(|fraction| fraction.iter())
+                        .all(legal)
             },
             "illegal characters in number; check its `impl Numeric`",
         );
 
-        let width_desired = self.width_desired(dynamic);
-        let mut decimal_pos = decimal_pos.unwrap_or_else(|| digits.len());
-        let mut digit_count = if self.align() == Align::Decimal {
-            decimal_pos
+        let unit = self.width_unit();
+        let exponent_width = exponent
+            .as_ref()
+            .map(|exponent| unit.str_width(exponent.as_str()))
+            .unwrap_or_default();
+        let width_desired = resolved.width_desired(exponent_width);
+        let separator = resolved.separator.map(|(separator, _)| separator);
+        let separator_width = resolved.separator_width;
+        // all digits are ASCII, so only a separator may not be a single column wide
+        let byte_width = |byte: u8| {
+            if byte == SEPARATOR {
+                separator_width
+            } else {
+                1
+            }
+        };
+        // width of the digits before the decimal
+        let mut integral_width = integer.iter().map(byte_width).sum::<usize>();
+        // width of the decimal and the digits after it
+        let fractional_width = fraction
+            .as_ref()
+            .map(|fraction| resolved.decimal_separator_width + fraction.len())
+            .unwrap_or_default();
+        let digit_width = |integral_width: usize| {
+            if self.align() == Align::Decimal {
+                integral_width
+            } else {
+                integral_width + fractional_width
+            }
+        };
+        // count of the digits before the decimal, not including separators
+        let mut integral_digits = integer.iter().filter(|&byte| byte != SEPARATOR).count();
+        // The final digit before the decimal is never extraneous, nor are the zeros required by
+        // the minimum integer digits.
+        let min_integer_digits = self.min_integer_digits().unwrap_or_default().max(1);
+        let extraneous = |byte: u8, integral_digits: usize| {
+            byte == SEPARATOR || (byte == b'0' && integral_digits > min_integer_digits)
+        };
+        // padding and separating can introduce extraneous leading 0 chars, so let's fix that.
+        while digit_width(integral_width) > width_desired
+            && integer.len() > 1
+            && extraneous(
+                integer.last().expect("can't be empty while len > 1"),
+                integral_digits,
+            )
+        {
+            let popped = integer.pop().expect("known not to be empty");
+            integral_width -= byte_width(popped);
+            if popped != SEPARATOR {
+                integral_digits -= 1;
+            }
+        }
+
+        let rendered_zero = integer.iter().all(|byte| byte == b'0' || byte == SEPARATOR)
+            && fraction
+                .iter()
+                .flat_map(|fraction| fraction.iter())
+                .all(|digit| digit == b'0');
+        let (sign_char, sign_suffix) = if rendered_zero {
+            let negative = match self.zero_sign() {
+                ZeroSign::Preserve => number.is_sign_negative() && self.base() == Base::Decimal,
+                _ => negative,
+            };
+            resolved.zero_signs[negative as usize]
         } else {
-            digits.len()
+            resolved.signs[negative as usize]
         };
-        // padding and separating can introduce extraneous leading 0 chars, so let's fix that
-        while digit_count > width_desired && {
-            let last = *digits.back().expect("can't be empty while decimal_pos > 0");
-            last == '0' || matches_separator(last)
-        } {
-            digit_count -= 1;
-            decimal_pos -= 1;
-            digits.pop_back();
-        }
-
-        let width_used = digits.len();
-        let (mut padding_front, padding_rear) = match self.align() {
-            Align::Right => (width_desired.saturating_sub(width_used), 0),
+        let sign_width = sign_char.map(|ch| unit.char_width(ch)).unwrap_or_default();
+        // make room for the sign among any padding zeros
+        let mut room = 0;
+        while room < sign_width
+            && integer.len() > 1
+            && extraneous(
+                integer.last().expect("known not to be empty"),
+                integral_digits,
+            )
+        {
+            let popped = integer.pop().expect("known not to be empty");
+            let popped_width = byte_width(popped);
+            integral_width -= popped_width;
+            room += popped_width;
+            if popped != SEPARATOR {
+                integral_digits -= 1;
+            }
+        }
+
+        let width_used = digit_width(integral_width) + sign_width;
+        let (padding_front, padding_rear) = match self.align() {
+            Align::Right | Align::AfterSign | Align::Decimal => {
+                (width_desired.saturating_sub(width_used), 0)
+            }
             Align::Left => (0, width_desired.saturating_sub(width_used)),
             Align::Center => {
                 let unused_width = width_desired.saturating_sub(width_used);
@@ -244,77 +1092,121 @@ impl NumFmt {
                 // bias right
                 (unused_width - half_unused_width, half_unused_width)
             }
-            Align::Decimal => (width_desired.saturating_sub(decimal_pos), 0),
         };
 
-        let sign_char = match (self.sign(), negative) {
-            (Sign::PlusAndMinus, _) => Some(if negative { '-' } else { '+' }),
-            (Sign::OnlyMinus, true) => Some('-'),
-            (Sign::OnlyMinus, false) => None,
-        };
-        if sign_char.is_some() {
-            padding_front = padding_front.saturating_sub(1);
-            if !digits.is_empty() {
-                let back = *digits.back().expect("known not to be empty");
-                if back == '0' || matches_separator(back) {
-                    digits.pop_back();
-                }
-            }
-        }
+        let (base_prefix, base_suffix) = resolved.base_affixes.get(self);
+        let currency_prefix = resolved.currency_prefix;
+        let currency_suffix = resolved.currency_suffix;
+        let sign_before_currency = resolved.sign_before_currency;
 
-        let prefix = match (self.hash(), self.base()) {
-            (false, _) => None,
-            (_, Base::Binary) => Some("0b"),
-            (_, Base::Octal) => Some("0o"),
-            (_, Base::Decimal) => Some("0d"),
-            (_, Base::LowerHex) | (_, Base::UpperHex) => Some("0x"),
-        };
-        if prefix.is_some() {
-            padding_front = padding_front.saturating_sub(2);
-        }
-
-        // constant 3 ensures that even with a sign and a prefix, we don't have to reallocate
-        let mut rendered = String::with_capacity(padding_front + padding_rear + width_used + 3);
+        // constant 3 ensures that even with a sign, we don't have to reallocate
+        let affix_len = resolved.affix_len
+            + exponent
+                .as_ref()
+                .map(|exponent| exponent.as_str().len())
+                .unwrap_or_default()
+            + sign_suffix.map(SignSuffix::len).unwrap_or_default();
+        let mut rendered = Measured::new(sink, unit, limit);
+        rendered.reserve(padding_front + padding_rear + width_used + affix_len + 3);
 
         // finally, assemble all the ingredients
         //
-        // the actual ordering depends on the configuration of `self.zero`:
-        // when `true`, it's sign -> prefix -> padding;
-        // when `false`, it's padding -> sign -> prefix
+        // the actual ordering depends on the configuration of `self.zero` and the alignment:
+        // for the zero handler and `Align::AfterSign`, it's prefix -> sign -> base prefix -> padding;
+        // otherwise, it's padding -> prefix -> sign -> base prefix
+        //
+        // a currency marker preceding the number goes before or after the sign, according
+        // to the currency's conventions
 
-        if !self.zero {
-            for _ in 0..padding_front {
-                rendered.push(self.fill());
-            }
+        let pad_after_sign = self.zero || self.align() == Align::AfterSign;
+        if !pad_after_sign {
+            self.push_padding(&mut rendered, self.fill_pattern(), padding_front);
         }
 
-        if let Some(sign) = sign_char {
-            rendered.push(sign);
+        if let Some(prefix) = self.prefix() {
+            rendered.push_str(PartKind::Prefix, prefix);
         }
-        if let Some(prefix) = prefix {
-            rendered.push_str(prefix);
+        if let Some(sign) = sign_char.filter(|_| sign_before_currency) {
+            rendered.push(PartKind::Sign, sign);
         }
-
-        if self.zero {
-            for _ in 0..padding_front {
-                rendered.push(self.fill());
+        if let Some((marker, spaced)) = currency_prefix {
+            rendered.push_str(PartKind::Currency, marker);
+            if spaced {
+                rendered.push(PartKind::Currency, ' ');
             }
         }
+        if let Some(sign) = sign_char.filter(|_| !sign_before_currency) {
+            rendered.push(PartKind::Sign, sign);
+        }
+        if let Some(prefix) = base_prefix {
+            rendered.push_str(PartKind::Prefix, prefix);
+        }
 
-        for digit in digits.into_iter().rev() {
-            rendered.push(digit);
+        if pad_after_sign {
+            self.push_padding(&mut rendered, self.fill_pattern(), padding_front);
         }
-        for _ in 0..padding_rear {
-            rendered.push(self.fill());
+
+        for byte in integer.iter().rev() {
+            match separator.filter(|_| byte == SEPARATOR) {
+                Some(separator) => rendered.push(PartKind::GroupSeparator, separator),
+                None => rendered.push(PartKind::Integer, char::from(byte)),
+            }
         }
+        if let Some(fraction) = &fraction {
+            rendered.push(PartKind::DecimalSeparator, decimal_separator);
+            for digit in fraction.iter() {
+                rendered.push(PartKind::Fraction, char::from(digit));
+            }
+        }
+        if let Some(exponent) = &exponent {
+            rendered.push_str(PartKind::Exponent, exponent.as_str());
+        }
+        if let Some(suffix) = base_suffix {
+            rendered.push_str(PartKind::Suffix, suffix);
+        }
+        if let Some(ratio) = self.ratio() {
+            rendered.push_str(PartKind::Suffix, ratio.suffix());
+        }
+        if let Some((marker, spaced)) = currency_suffix {
+            if spaced {
+                rendered.push(PartKind::Currency, ' ');
+            }
+            rendered.push_str(PartKind::Currency, marker);
+        }
+        if let Some(suffix) = sign_suffix {
+            suffix.push_to(&mut rendered);
+        }
+        if let Some(suffix) = self.suffix() {
+            rendered.push_str(PartKind::Suffix, suffix);
+        }
+        self.push_padding(&mut rendered, self.right_fill_pattern(), padding_rear);
 
-        Ok(rendered)
+        Ok(rendered.width())
     }
 
     /// `char` used to pad the extra space when the rendered number is smaller than the `width`.
+    ///
+    /// If a fill pattern is configured, this is its first `char`.
     #[inline]
     pub fn fill(&self) -> char {
-        self.fill.unwrap_or(' ')
+        self.fill_pattern().chars().next().unwrap_or(' ')
+    }
+
+    /// Pattern repeated to pad the extra space when the rendered number is smaller than the
+    /// `width`.
+    #[inline]
+    pub fn fill_pattern(&self) -> &str {
+        self.fill.as_deref().unwrap_or(" ")
+    }
+
+    /// Pattern repeated to pad the extra space after the rendered number.
+    ///
+    /// Defaults to the [`fill_pattern`][NumFmt::fill_pattern].
+    #[inline]
+    pub fn right_fill_pattern(&self) -> &str {
+        self.right_fill
+            .as_deref()
+            .unwrap_or_else(|| self.fill_pattern())
     }
 
     /// Desired alignment.
@@ -329,46 +1221,228 @@ impl NumFmt {
         self.sign
     }
 
+    /// `char` used as the minus sign.
+    #[inline]
+    pub fn minus_sign(&self) -> char {
+        self.minus_sign.unwrap_or('-')
+    }
+
+    /// `char` used as the plus sign.
+    #[inline]
+    pub fn plus_sign(&self) -> char {
+        self.plus_sign.unwrap_or('+')
+    }
+
+    /// How the sign of a number which renders as zero is printed.
+    #[inline]
+    pub fn zero_sign(&self) -> ZeroSign {
+        self.zero_sign
+    }
+
     /// Whether to print a base specification before the number.
     #[inline]
     pub fn hash(&self) -> bool {
         self.hash
     }
 
+    /// Style of the base specification printed when [`hash`][NumFmt::hash] is set.
+    #[inline]
+    pub fn base_marker(&self) -> &BaseMarker {
+        &self.base_marker
+    }
+
+    /// Literal text printed before the number.
+    #[inline]
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Literal text printed after the number.
+    #[inline]
+    pub fn suffix(&self) -> Option<&str> {
+        self.suffix.as_deref()
+    }
+
     /// Whether the zero formatter was used.
     #[inline]
     pub fn zero(&self) -> bool {
-        self.zero && self.fill() == '0'
+        self.zero && self.fill_pattern() == "0"
     }
 
-    /// Configured render width in bytes.
+    /// Configured render width, in the configured [`width_unit`][NumFmt::width_unit].
     #[inline]
     pub fn width(&self) -> usize {
         self.width
     }
 
+    /// Unit in which `width` and padding are measured. See [`WidthUnit`].
+    #[inline]
+    pub fn width_unit(&self) -> WidthUnit {
+        self.width_unit
+    }
+
+    /// Minimum count of digits before the decimal, if any.
+    ///
+    /// Numbers with fewer integer digits are padded with leading zeros, independently of the
+    /// `width`.
+    #[inline]
+    pub fn min_integer_digits(&self) -> Option<usize> {
+        self.min_integer_digits
+    }
+
+    /// Maximum count of digits before the decimal, if any.
+    ///
+    /// Numbers with more integer digits keep only the low-order digits.
+    #[inline]
+    pub fn max_integer_digits(&self) -> Option<usize> {
+        self.max_integer_digits
+    }
+
+    /// Maximum render width, if any, in the configured [`width_unit`][NumFmt::width_unit].
+    #[inline]
+    pub fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// What to do when the rendered number exceeds the [`max_width`][NumFmt::max_width].
+    #[inline]
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
     /// Configured post-decimal precision in bytes.
     ///
     /// Precision will pad or truncate as required if set. If unset, passes through as many
-    /// digits past the decimal as the underlying type naturally returns.
+    /// digits past the decimal as the underlying type naturally returns, unless a
+    /// [`currency`][NumFmt::currency] is configured, in which case it defaults to the
+    /// currency's minor units.
     #[inline]
     pub fn precision(&self) -> Option<usize> {
         self.precision
     }
 
+    /// Minimum count of digits after the decimal, if configured independently of the
+    /// [`precision`][NumFmt::precision].
+    ///
+    /// Trailing zeros beyond this count are trimmed.
+    #[inline]
+    pub fn min_fraction_digits(&self) -> Option<usize> {
+        self.min_fraction_digits
+    }
+
+    /// Maximum count of digits after the decimal, if configured independently of the
+    /// [`precision`][NumFmt::precision].
+    #[inline]
+    pub fn max_fraction_digits(&self) -> Option<usize> {
+        self.max_fraction_digits
+    }
+
+    /// Whether the decimal point is printed even when no digits follow it.
+    #[inline]
+    pub fn force_decimal_point(&self) -> bool {
+        self.force_decimal_point
+    }
+
     /// Configured output format.
     #[inline]
     pub fn base(&self) -> Base {
         self.base
     }
 
+    /// How the magnitude of the number is written. See [`Notation`].
+    #[inline]
+    pub fn notation(&self) -> Notation {
+        self.notation
+    }
+
+    /// How the exponent of a number in scientific notation is written. See [`ExponentStyle`].
+    ///
+    /// This also applies when falling back to scientific notation on
+    /// [`Overflow::Scientific`].
+    #[inline]
+    pub fn exponent_style(&self) -> ExponentStyle {
+        self.exponent_style
+    }
+
+    /// Configured ratio, if any. See [`Ratio`].
+    #[inline]
+    pub fn ratio(&self) -> Option<Ratio> {
+        self.ratio
+    }
+
+    /// Configured scale: the power of ten by which the number is multiplied before rendering.
+    ///
+    /// This does not include the scaling implied by the [`ratio`][NumFmt::ratio].
+    #[inline]
+    pub fn scale(&self) -> i32 {
+        self.scale
+    }
+
+    /// Total power of ten by which the number is multiplied before rendering, if it fits an `i32`.
+    fn scale_exponent(&self) -> Option<i32> {
+        self.scale
+            .checked_add(self.ratio().map(Ratio::exponent).unwrap_or_default())
+    }
+
+    /// Configured currency, if any. See [`Currency`].
+    #[inline]
+    pub fn currency(&self) -> Option<Currency> {
+        self.currency
+    }
+
+    /// Which marker identifies the configured currency. See [`CurrencyDisplay`].
+    #[inline]
+    pub fn currency_display(&self) -> CurrencyDisplay {
+        self.currency_display
+    }
+
+    /// Sign printed before the number and sign printed after the number.
+    ///
+    /// Every style of sign produces a suffix of the same width for positive and negative
+    /// numbers, so that the numbers line up in columns.
+    fn sign_affixes(&self, negative: bool) -> SignAffixes {
+        let minus = self.minus_sign();
+        match (self.sign(), negative) {
+            (Sign::PlusAndMinus, true) => (Some(minus), None),
+            (Sign::PlusAndMinus, false) => (Some(self.plus_sign()), None),
+            (Sign::OnlyMinus, true) => (Some(minus), None),
+            (Sign::OnlyMinus, false) => (None, None),
+            (Sign::SpaceAndMinus, true) => (Some(minus), None),
+            (Sign::SpaceAndMinus, false) => (Some(' '), None),
+            (Sign::Parentheses, true) => (Some('('), Some(SignSuffix::Char(')'))),
+            (Sign::Parentheses, false) => (None, Some(SignSuffix::Char(' '))),
+            (Sign::TrailingMinus, true) => (None, Some(SignSuffix::Char(minus))),
+            (Sign::TrailingMinus, false) => (None, Some(SignSuffix::Char(' '))),
+            (Sign::CreditDebit, true) => (None, Some(SignSuffix::Str(" CR"))),
+            (Sign::CreditDebit, false) => (None, Some(SignSuffix::Str("   "))),
+        }
+    }
+
+    /// Base specification printed before and after the number, if `hash` is set.
+    fn base_affixes(&self) -> BaseAffixes {
+        if !self.hash() {
+            return BaseAffixes::Static(None, None);
+        }
+        match self.base_marker().static_affixes(self.base()) {
+            Some((prefix, suffix)) => BaseAffixes::Static(non_empty(prefix), non_empty(suffix)),
+            None => BaseAffixes::Custom,
+        }
+    }
+
+    /// Currency marker, and whether a space separates it from the number.
+    fn currency_marker(&self) -> Option<(SymbolPosition, (&'static str, bool))> {
+        let currency = self.currency()?;
+        Some((currency.position, currency.marker(self.currency_display())))
+    }
+
     /// Configured group separator and spacing.
     ///
     /// If one or the other of these is set, the other will adopt
     /// an appropriate default. However, if neither is configured, then
-    /// no group separation will be performed.
+    /// no group separation will be performed. Neither is it if the spacing is 0.
     fn separator_and_spacing_with(&self, dynamic: Dynamic) -> Option<(char, usize)> {
         match (self.separator, self.spacing_with(dynamic)) {
+            (_, Some(0)) => None,
             (Some(sep), Some(spc)) => Some((sep, spc)),
             (Some(sep), None) => Some((sep, 3)),
             (None, Some(spc)) => Some((',', spc)),
@@ -403,12 +1477,33 @@ impl NumFmt {
         self.decimal_separator.unwrap_or('.')
     }
 
-    fn width_with(&self, dynamic: Dynamic) -> usize {
-        dynamic.width.unwrap_or(self.width)
+    pub(crate) fn width_with(&self, dynamic: Dynamic) -> usize {
+        let width = dynamic.width.unwrap_or(self.width);
+        match self.max_width() {
+            Some(max_width) => width.min(max_width),
+            None => width,
+        }
     }
 
     fn precision_with(&self, dynamic: Dynamic) -> Option<usize> {
-        dynamic.precision.or(self.precision)
+        dynamic
+            .precision
+            .or(self.precision)
+            .or_else(|| self.currency().map(|currency| currency.minor_units))
+    }
+
+    /// Minimum and maximum count of digits after the decimal.
+    ///
+    /// The precision sets both, unless they are explicitly configured. Dynamic precision
+    /// overrides everything.
+    fn fraction_digits_with(&self, dynamic: Dynamic) -> (usize, Option<usize>) {
+        if let Some(precision) = dynamic.precision {
+            return (precision, Some(precision));
+        }
+        let precision = self.precision_with(dynamic);
+        let min = self.min_fraction_digits().or(precision).unwrap_or_default();
+        let max = self.max_fraction_digits().or(precision);
+        (min, max.map(|max| max.max(min)))
     }
 
     fn spacing_with(&self, dynamic: Dynamic) -> Option<usize> {
@@ -416,15 +1511,16 @@ impl NumFmt {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl FromStr for NumFmt {
-    type Err = parse::Error;
+    type Err = parse::Error<'static>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse::parse(s)
+        parse::parse(s).map_err(parse::Error::into_owned)
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
 
@@ -1,7 +1,17 @@
 //! This module contains implementations of [`Numeric`][crate::Numeric] for several types, plus helpers which can
 //! ease implementation for your own type.
-
-use std::ops::{BitAnd, ShrAssign};
+//!
+//! The primitive types write their digits directly into a stack buffer: see [`Digits`] and
+//! [`FloatDigits`]. The generic helpers [`BinIter`], [`OctIter`], [`HexIter`] and [`DecIter`] are
+//! slower, but only require a few standard traits of your type.
+
+#[cfg(feature = "alloc")]
+use alloc::{string::ToString, vec::Vec};
+use core::{
+    convert::TryFrom,
+    fmt::{self, Write},
+    ops::{BitAnd, ShrAssign},
+};
 
 macro_rules! impl_iter {
     ($iter:ident) => {
@@ -53,7 +63,7 @@ macro_rules! impl_iter {
                 }
                 panic!(
                     "no digit matched when computing {}",
-                    std::any::type_name::<$iter<N>>()
+                    core::any::type_name::<$iter<N>>()
                 );
             }
         }
@@ -93,8 +103,10 @@ impl_iter!(HexIter);
 /// Iterator over the decimal digits of a number.
 ///
 /// This implementation defers to the standard `format!` macro to determine the digits of the number.
+#[cfg(feature = "alloc")]
 pub struct DecIter(Vec<char>);
 
+#[cfg(feature = "alloc")]
 impl DecIter {
     /// Create iterators over the digits of a number left and right of the decimal respectively.
     ///
@@ -110,7 +122,7 @@ impl DecIter {
         N: ToString,
     {
         let s = n.to_string();
-        debug_assert!(s.chars().all(|c| c == '.' || ('0'..='9').contains(&c)));
+        debug_assert!(s.chars().all(|c| c == '.' || c.is_ascii_digit()));
         debug_assert!(s.chars().filter(|&c| c == '.').count() <= 1);
         let mut found_decimal = false;
         let (left, mut right): (Vec<_>, Vec<_>) = s.chars().partition(|&c| {
@@ -132,6 +144,7 @@ impl DecIter {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Iterator for DecIter {
     type Item = char;
 
@@ -140,86 +153,342 @@ impl Iterator for DecIter {
     }
 }
 
-macro_rules! impl_for {
-    (unsigned_int $type:ident) => {
-        mod $type {
-            use super::{BinIter, DecIter, HexIter, OctIter};
-            use crate::Numeric;
+/// Pairs of decimal digits, from `00` to `99`.
+const DECIMAL_PAIRS: &[u8; 200] = b"\
+    0001020304050607080910111213141516171819\
+    2021222324252627282930313233343536373839\
+    4041424344454647484950515253545556575859\
+    6061626364656667686970717273747576777879\
+    8081828384858687888990919293949596979899";
 
-            impl Numeric for $type {
-                type BinIter = BinIter<$type>;
-                type OctIter = OctIter<$type>;
-                type DecLeftIter = DecIter;
-                type DecRightIter = DecIter;
-                type HexIter = HexIter<$type>;
+/// Digits of the bases which are powers of two, indexed by value.
+const RADIX_DIGITS: &[u8; 16] = b"0123456789abcdef";
 
-                fn binary(&self) -> Option<Self::BinIter> {
-                    Some(BinIter::new(*self))
-                }
+/// `10**19`, the greatest power of 10 which fits in a `u64`.
+const TEN_POW_19: u128 = 10_000_000_000_000_000_000;
 
-                fn octal(&self) -> Option<Self::OctIter> {
-                    Some(OctIter::new(*self))
-                }
+/// Digits of a primitive number, written directly into a stack buffer.
+///
+/// Iterates from the least to the most significant digit, as required of most iterators of
+/// [`Numeric`][crate::Numeric]; iterate in reverse for the opposite. `CAP` is the capacity of
+/// the buffer.
+#[derive(Clone, Debug)]
+pub struct Digits<const CAP: usize> {
+    buf: [u8; CAP],
+    /// The remaining digits occupy `buf[start..end]`, most significant first.
+    start: usize,
+    end: usize,
+}
 
-                fn hex(&self) -> Option<Self::HexIter> {
-                    Some(HexIter::new(*self))
-                }
+impl<const CAP: usize> Digits<CAP> {
+    fn empty() -> Self {
+        Digits {
+            buf: [0; CAP],
+            start: CAP,
+            end: CAP,
+        }
+    }
 
-                fn decimal(&self) -> (Self::DecLeftIter, Option<Self::DecRightIter>) {
-                    DecIter::new(*self)
-                }
+    /// Digits of `n` in decimal. Zero has the single digit `0`.
+    ///
+    /// `CAP` must be at least the count of digits of `n`.
+    fn decimal(n: u128) -> Self {
+        let mut digits = Self::empty();
+        let mut n = n;
+        // 128-bit division is slow, so divide into chunks which fit in a `u64`
+        while n > u64::MAX as u128 {
+            digits.push_decimal((n % TEN_POW_19) as u64, 19);
+            n /= TEN_POW_19;
+        }
+        digits.push_decimal(n as u64, 1);
+        digits
+    }
 
-                fn is_negative(&self) -> bool {
-                    false
+    /// Digits of `n` in base `2**bits`. Zero has no digits at all.
+    ///
+    /// `CAP` must be at least the count of digits of `n`, and `bits` at most 4.
+    fn radix(n: u128, bits: u32) -> Self {
+        let mut digits = Self::empty();
+        let mask = (1 << bits) - 1;
+        match u64::try_from(n) {
+            Ok(mut n) => {
+                while n != 0 {
+                    digits.push(RADIX_DIGITS[n as usize & mask]);
+                    n >>= bits;
                 }
             }
+            Err(_) => {
+                let mut n = n;
+                while n != 0 {
+                    digits.push(RADIX_DIGITS[n as usize & mask]);
+                    n >>= bits;
+                }
+            }
+        }
+        digits
+    }
+
+    /// Digits copied from an ASCII string, most significant first.
+    fn from_ascii(ascii: &[u8]) -> Self {
+        let mut digits = Self::empty();
+        digits.start -= ascii.len();
+        digits.buf[digits.start..].copy_from_slice(ascii);
+        digits
+    }
+
+    /// Prepend a more significant digit.
+    #[inline]
+    fn push(&mut self, digit: u8) {
+        self.start -= 1;
+        self.buf[self.start] = digit;
+    }
+
+    /// Prepend the decimal digits of `n`, padded with zeros to at least `min_len` digits.
+    fn push_decimal(&mut self, mut n: u64, min_len: usize) {
+        let end = self.start;
+        while n >= 100 {
+            let pair = (n % 100) as usize * 2;
+            n /= 100;
+            self.start -= 2;
+            self.buf[self.start..self.start + 2].copy_from_slice(&DECIMAL_PAIRS[pair..pair + 2]);
+        }
+        if n >= 10 {
+            let pair = n as usize * 2;
+            self.push(DECIMAL_PAIRS[pair + 1]);
+            self.push(DECIMAL_PAIRS[pair]);
+        } else {
+            self.push(b'0' + n as u8);
+        }
+        while end - self.start < min_len {
+            self.push(b'0');
+        }
+    }
+
+    /// The remaining digits, most significant first.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[self.start..self.end]).expect("digits are always ASCII")
+    }
+}
+
+impl<const CAP: usize> Iterator for Digits<CAP> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(self.buf[self.end] as char)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<const CAP: usize> DoubleEndedIterator for Digits<CAP> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        Some(self.buf[self.start - 1] as char)
+    }
+}
+
+impl<const CAP: usize> ExactSizeIterator for Digits<CAP> {}
+
+/// Capacity of the significant digits of a [`FloatDigits`]; enough for any `f64`.
+const FLOAT_SIGNIFICANT_DIGITS: usize = 17;
+
+/// Decimal digits of a floating point number, iterated away from the decimal point.
+///
+/// The digits are the shortest which round-trip, as printed by the standard library. Zeros
+/// between the decimal point and the significant digits are generated on the fly, so very large
+/// and very small numbers are cheap.
+#[derive(Clone, Debug)]
+pub struct FloatDigits {
+    /// Zeros between the decimal point and the significant digits.
+    zeros: usize,
+    significant: Digits<FLOAT_SIGNIFICANT_DIGITS>,
+    /// Iterate the significant digits from the most significant.
+    forward: bool,
+}
+
+impl FloatDigits {
+    /// Create iterators over the digits of a number left and right of the decimal respectively.
+    ///
+    /// `n` must be finite and not negative.
+    fn new(n: impl fmt::LowerExp) -> (FloatDigits, Option<FloatDigits>) {
+        // i.e. `1.2345e-6`; the standard library prints the same digits as `Display` would
+        let mut formatted = StackBuf::default();
+        write!(formatted, "{:e}", n).expect("scientific notation of a float fits in the buffer");
+        let formatted = formatted.as_bytes();
+        let e_pos = formatted.iter().position(|&b| b == b'e');
+        debug_assert!(e_pos.is_some(), "n must be finite");
+        let e_pos = e_pos.unwrap_or(formatted.len());
+        let exponent: isize = core::str::from_utf8(&formatted[e_pos..])
+            .ok()
+            .and_then(|exponent| exponent.get(1..)?.parse().ok())
+            .unwrap_or_default();
+
+        let mut significant = [0; FLOAT_SIGNIFICANT_DIGITS];
+        let mut len = 0;
+        for &b in formatted[..e_pos].iter().filter(|b| b.is_ascii_digit()) {
+            significant[len] = b;
+            len += 1;
         }
+        let significant = &significant[..len];
+
+        if exponent < 0 {
+            let left = FloatDigits {
+                zeros: 0,
+                significant: Digits::from_ascii(b"0"),
+                forward: false,
+            };
+            let right = FloatDigits {
+                zeros: (-exponent - 1) as usize,
+                significant: Digits::from_ascii(significant),
+                forward: true,
+            };
+            return (left, Some(right));
+        }
+
+        let integral_len = exponent as usize + 1;
+        let split = integral_len.min(len);
+        let left = FloatDigits {
+            zeros: integral_len - split,
+            significant: Digits::from_ascii(&significant[..split]),
+            forward: false,
+        };
+        let right = Some(&significant[split..])
+            .filter(|fraction| !fraction.is_empty())
+            .map(|fraction| FloatDigits {
+                zeros: 0,
+                significant: Digits::from_ascii(fraction),
+                forward: true,
+            });
+        (left, right)
+    }
+}
+
+impl Iterator for FloatDigits {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.zeros > 0 {
+            self.zeros -= 1;
+            return Some('0');
+        }
+        if self.forward {
+            self.significant.next_back()
+        } else {
+            self.significant.next()
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.zeros + self.significant.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for FloatDigits {}
+
+/// Fixed-capacity buffer for the output of the standard formatting machinery.
+#[derive(Default)]
+struct StackBuf {
+    buf: [u8; 32],
+    len: usize,
+}
+
+impl StackBuf {
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl fmt::Write for StackBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+macro_rules! impl_for {
+    (unsigned_int $type:ident) => {
+        impl_for!(@int $type, |n: &$type| *n as u128, |n: &$type| *n as u128, |_n: &$type| false);
     };
-    (signed_int $type:ident) => {
+    (signed_int $type:ident as $unsigned:ident) => {
+        // as in the standard library, the bases other than decimal render the two's complement
+        impl_for!(
+            @int $type,
+            |n: &$type| *n as $unsigned as u128,
+            |n: &$type| n.unsigned_abs() as u128,
+            |n: &$type| *n < 0
+        );
+    };
+    (@int $type:ident, $bits:expr, $magnitude:expr, $is_negative:expr) => {
         mod $type {
-            use super::{BinIter, DecIter, HexIter, OctIter};
+            use super::Digits;
             use crate::Numeric;
 
+            const BINARY_DIGITS: usize = $type::BITS as usize;
+            const OCTAL_DIGITS: usize = BINARY_DIGITS.div_ceil(3);
+            const DECIMAL_DIGITS: usize = BINARY_DIGITS * 3 / 10 + 1;
+            const HEX_DIGITS: usize = BINARY_DIGITS / 4;
+
             impl Numeric for $type {
-                type BinIter = BinIter<$type>;
-                type OctIter = OctIter<$type>;
-                type DecLeftIter = DecIter;
-                type DecRightIter = DecIter;
-                type HexIter = HexIter<$type>;
+                type BinIter = Digits<BINARY_DIGITS>;
+                type OctIter = Digits<OCTAL_DIGITS>;
+                type DecLeftIter = Digits<DECIMAL_DIGITS>;
+                type DecRightIter = core::iter::Empty<char>;
+                type HexIter = Digits<HEX_DIGITS>;
 
                 fn binary(&self) -> Option<Self::BinIter> {
-                    Some(BinIter::new(*self))
+                    Some(Digits::radix($bits(self), 1))
                 }
 
                 fn octal(&self) -> Option<Self::OctIter> {
-                    Some(OctIter::new(*self))
+                    Some(Digits::radix($bits(self), 3))
                 }
 
                 fn hex(&self) -> Option<Self::HexIter> {
-                    Some(HexIter::new(*self))
+                    Some(Digits::radix($bits(self), 4))
                 }
 
                 fn decimal(&self) -> (Self::DecLeftIter, Option<Self::DecRightIter>) {
-                    DecIter::new(self.abs())
+                    (Digits::decimal($magnitude(self)), None)
                 }
 
                 fn is_negative(&self) -> bool {
-                    *self < 0
+                    $is_negative(self)
                 }
             }
         }
     };
     (float $type:ident) => {
         mod $type {
-            use super::DecIter;
+            use super::FloatDigits;
             use crate::Numeric;
-            use std::iter::Empty;
+            use core::iter::Empty;
 
             impl Numeric for $type {
                 type BinIter = Empty<char>;
                 type OctIter = Empty<char>;
-                type DecLeftIter = DecIter;
-                type DecRightIter = DecIter;
+                type DecLeftIter = FloatDigits;
+                type DecRightIter = FloatDigits;
                 type HexIter = Empty<char>;
 
                 fn binary(&self) -> Option<Self::BinIter> {
@@ -235,12 +504,26 @@ macro_rules! impl_for {
                 }
 
                 fn decimal(&self) -> (Self::DecLeftIter, Option<Self::DecRightIter>) {
-                    DecIter::new(self.abs())
+                    // `abs` is only available in `core` from Rust 1.85
+                    let magnitude = if self.is_sign_negative() { -*self } else { *self };
+                    FloatDigits::new(magnitude)
                 }
 
                 fn is_negative(&self) -> bool {
                     *self < 0.0
                 }
+
+                fn is_sign_negative(&self) -> bool {
+                    $type::is_sign_negative(*self)
+                }
+
+                fn is_nan(&self) -> bool {
+                    $type::is_nan(*self)
+                }
+
+                fn is_infinite(&self) -> bool {
+                    $type::is_infinite(*self)
+                }
             }
         }
     };
@@ -252,12 +535,12 @@ impl_for!(unsigned_int u32);
 impl_for!(unsigned_int u64);
 impl_for!(unsigned_int u128);
 impl_for!(unsigned_int usize);
-// TODO: impl for i8
-impl_for!(signed_int i16);
-impl_for!(signed_int i32);
-impl_for!(signed_int i64);
-impl_for!(signed_int i128);
-impl_for!(signed_int isize);
+impl_for!(signed_int i8 as u8);
+impl_for!(signed_int i16 as u16);
+impl_for!(signed_int i32 as u32);
+impl_for!(signed_int i64 as u64);
+impl_for!(signed_int i128 as u128);
+impl_for!(signed_int isize as usize);
 impl_for!(float f32);
 impl_for!(float f64);
 
@@ -265,6 +548,7 @@ impl_for!(float f64);
 mod tests {
     macro_rules! suite_for {
         (dec: int $( $type_int:ident ),+ ; float $( $type_float:ident ),+ ) => {
+            #[cfg(feature = "alloc")]
             #[allow(non_snake_case)]
             mod DecIter {
                 use super::super::DecIter as Iter;
@@ -343,4 +627,91 @@ mod tests {
     suite_for!(OctIter, "{:o}", u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
     suite_for!(HexIter, "{:x}", u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
     suite_for!(dec: int u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize; float f32, f64);
+
+    /// Render the digits of a `Numeric` implementation most significant first.
+    fn decimal<N: crate::Numeric>(n: &N) -> String {
+        let (left, right) = n.decimal();
+        let mut digits: String = left.collect::<Vec<_>>().into_iter().rev().collect();
+        if let Some(right) = right {
+            digits.push('.');
+            digits.extend(right);
+        }
+        digits
+    }
+
+    fn radix<I: Iterator<Item = char>>(digits: Option<I>) -> String {
+        let digits: String = digits
+            .unwrap()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect();
+        if digits.is_empty() {
+            "0".into()
+        } else {
+            digits
+        }
+    }
+
+    macro_rules! numeric_suite {
+        (int $( $type:ident ),+ ; float $( $type_float:ident ),+ ) => {
+            mod numeric {
+                use super::{decimal, radix};
+                use crate::Numeric;
+
+                $(
+                    #[test]
+                    fn $type() {
+                        let numbers = [
+                            0,
+                            1,
+                            7,
+                            10,
+                            99,
+                            100,
+                            $type::MAX / 3,
+                            $type::MAX - 1,
+                            $type::MAX,
+                            $type::MIN,
+                            $type::MIN / 7,
+                        ];
+                        for n in numbers.iter() {
+                            let magnitude = format!("{}", n);
+                            assert_eq!(decimal(n), magnitude.trim_start_matches('-'));
+                            assert_eq!(radix(n.binary()), format!("{:b}", n));
+                            assert_eq!(radix(n.octal()), format!("{:o}", n));
+                            assert_eq!(radix(n.hex()), format!("{:x}", n));
+                        }
+                    }
+                )+
+
+                $(
+                    #[test]
+                    fn $type_float() {
+                        let numbers = [
+                            0.0,
+                            0.5,
+                            1.0,
+                            1.5,
+                            1234.5678,
+                            0.000123,
+                            6.25e23,
+                            $type_float::MAX,
+                            $type_float::MIN_POSITIVE,
+                            $type_float::EPSILON,
+                            -2.75,
+                        ];
+                        for n in numbers.iter() {
+                            assert_eq!(decimal(n), format!("{}", n.abs()));
+                        }
+                    }
+                )+
+            }
+        };
+    }
+
+    numeric_suite!(
+        int u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize;
+        float f32, f64
+    );
 }
@@ -92,4 +92,26 @@ pub trait Numeric {
 
     /// `true` when this value is less than 0.
     fn is_negative(&self) -> bool;
+
+    /// `true` when this value has a negative sign.
+    ///
+    /// This differs from [`is_negative`][Numeric::is_negative] only for types which can
+    /// represent a negative zero, such as `f64`.
+    fn is_sign_negative(&self) -> bool {
+        self.is_negative()
+    }
+
+    /// `true` when this value is not a number, such as `f64::NAN`.
+    ///
+    /// A value which is not a number is rendered as `NaN` instead of its digits.
+    fn is_nan(&self) -> bool {
+        false
+    }
+
+    /// `true` when this value is positive or negative infinity.
+    ///
+    /// An infinite value is rendered as `inf` instead of its digits, after any sign.
+    fn is_infinite(&self) -> bool {
+        false
+    }
 }
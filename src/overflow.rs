@@ -0,0 +1,24 @@
+/// What to do when a rendered number is wider than the configured maximum width.
+///
+/// - `Extend`: print the number anyway, exceeding the maximum width (default)
+/// - `Error`: return [`Error::Overflow`][crate::Error::Overflow]
+/// - `Fill`: print the maximum width's worth of `#`, as spreadsheets do
+/// - `Truncate`: cut off the end of the output and mark the cut with `…`
+/// - `Scientific`: render the number in scientific notation, i.e. `1.23e9`, with as much
+///   precision as fits. If the number cannot fit even so, or is not rendered in decimal,
+///   behaves like `Fill`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Overflow {
+    Extend,
+    Error,
+    Fill,
+    Truncate,
+    Scientific,
+}
+
+impl Default for Overflow {
+    #[inline]
+    fn default() -> Self {
+        Self::Extend
+    }
+}
@@ -1,109 +1,756 @@
-use crate::{Align, Base, NumFmt, Sign};
-use lazy_static::lazy_static;
-use regex::Regex;
-
-lazy_static! {
-    static ref PARSE_RE: Regex = Regex::new(
-        r"(?x)
-        ^
-        (
-            (?P<fill>.)?
-            (?P<align>[<^>v])
-        )?
-        (?P<sign>[-+])?
-        (?P<hash>(?-x:#))?
-        (
-         (?P<zero>0)?
-         (?P<width>[1-9]\d*)
-        )?
-        (
-         \.
-         (?P<precision>\d+)
-        )?
-        (?P<format>[bodxX])?
-        (
-         (?P<separator>(?-x:[_, ]))
-         (?P<spacing>\d+)?
-        )?
-        $"
-    )
-    .unwrap();
-}
-
-#[derive(Debug, thiserror::Error, PartialEq, Eq)]
-pub enum Error {
-    #[error("Input did not match canonical format string regex")]
-    NoMatch,
-    #[error("failed to parse integer value \"{0}\"")]
-    ParseInt(String, #[source] std::num::ParseIntError),
+//! Parsing of format strings.
+//!
+//! See the crate-level documentation for the grammar.
+
+use crate::{
+    text::Text, Align, Base, BaseMarker, CurrencyDisplay, ExponentStyle, Notation, NumFmt,
+    Overflow, Ratio, Sign, WidthUnit, ZeroSign,
+};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{fmt, ops::Range};
+
+/// A component of the format string grammar.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Component {
+    Align,
+    Sign,
+    ZeroSign,
+    Hash,
+    Width,
+    Precision,
+    Format,
+    Separator,
+}
+
+/// Count of [`Component`]s.
+const COMPONENTS: usize = 8;
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Align => "alignment",
+            Self::Sign => "sign",
+            Self::ZeroSign => "zero sign",
+            Self::Hash => "`#` flag",
+            Self::Width => "width",
+            Self::Precision => "precision",
+            Self::Format => "format type",
+            Self::Separator => "separator",
+        })
+    }
+}
+
+/// What the parser expected where a format string departed from the grammar.
+///
+/// Most variants name the earliest component of the grammar which could have appeared at that
+/// point; any later component could have appeared there too.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Expected {
+    /// `[[fill]align]`, or any later component.
+    Align,
+    /// `sign`, or any later component.
+    Sign,
+    /// `zero_sign`, or any later component.
+    ZeroSign,
+    /// `#`, or any later component.
+    Hash,
+    /// `[['0']width]`, or any later component.
+    Width,
+    /// `'.' precision`, or any later component.
+    Precision,
+    /// `format`, or any later component.
+    Format,
+    /// `separator`, or the end of the format string.
+    Separator,
+    /// `spacing`, or the end of the format string.
+    Spacing,
+    /// The end of the format string.
+    End,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Align => "an alignment, or any later component",
+            Self::Sign => "a sign, or any later component",
+            Self::ZeroSign => "a zero sign, or any later component",
+            Self::Hash => "`#`, or any later component",
+            Self::Width => "a width, or any later component",
+            Self::Precision => "a precision, or any later component",
+            Self::Format => "a format, or any later component",
+            Self::Separator => "a separator, or the end of the format string",
+            Self::Spacing => "a spacing, or the end of the format string",
+            Self::End => "the end of the format string",
+        })
+    }
+}
+
+/// The way in which a format string departs from the grammar.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ErrorKind {
+    /// A `char` which can't appear at this point of the format string.
+    Unexpected { found: char, expected: Expected },
+    /// An unknown letter where the format type could appear, i.e. `f`.
+    UnknownFormat(char),
+    /// A component which belongs earlier in the format string, i.e. the sign in `x+`.
+    Misplaced(Component),
+    /// A component which appears more than once, i.e. the format type in `xe`.
+    Duplicate(Component),
+    /// The zero flag, not followed by a width.
+    MissingWidth,
+    /// Zeros leading the width, following the zero flag.
+    LeadingZero,
+    /// A `.`, not followed by the precision.
+    MissingPrecision,
+    /// A spacing of 0, i.e. in `,0`.
+    ZeroSpacing,
+    /// A number which does not fit in a `usize`.
+    TooLarge,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unexpected { found, expected } => {
+                write!(f, "unexpected `{}`: expected {}", found, expected)
+            }
+            Self::UnknownFormat(ch) => write!(f, "unknown format type `{}`", ch),
+            Self::Misplaced(component) => {
+                write!(
+                    f,
+                    "the {} must come earlier in the format string",
+                    component
+                )
+            }
+            Self::Duplicate(component) => write!(f, "duplicate {}", component),
+            Self::MissingWidth => f.write_str("the zero flag must be followed by a width"),
+            Self::LeadingZero => {
+                f.write_str("width must not start with 0 unless it is the zero flag")
+            }
+            Self::MissingPrecision => f.write_str("`.` must be followed by the precision"),
+            Self::ZeroSpacing => f.write_str("spacing must not be 0"),
+            Self::TooLarge => f.write_str("number is too large"),
+        }
+    }
+}
+
+/// A correction of the [span][Error::span] of an [`Error`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Suggestion {
+    /// Remove the span.
+    Remove,
+    /// Replace the span with this text.
+    Replace(&'static str),
+    /// Move the span to this byte offset, which precedes it.
+    Move(usize),
+}
+
+/// Format string in which an [`Error`] occurred.
+#[derive(Clone)]
+enum Input<'a> {
+    Borrowed(&'a str),
+    #[cfg(feature = "alloc")]
+    Owned(String),
+}
+
+impl Input<'_> {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Borrowed(input) => input,
+            #[cfg(feature = "alloc")]
+            Self::Owned(input) => input,
+        }
+    }
+}
+
+impl PartialEq for Input<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Input<'_> {}
+
+impl fmt::Debug for Input<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Failure to parse a format string.
+///
+/// Its `Display` implementation renders a diagnostic pointing out the offending span of the
+/// input, and a corrected format string if there is a suggestion:
+///
+/// ```rust
+/// # use num_runtime_fmt::NumFmt;
+/// let err = NumFmt::from_str(">10.2f").unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "unknown format type `f`\n  >10.2f\n       ^\nhelp: did you mean `>10.2`?",
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Error<'a> {
+    input: Input<'a>,
+    span: (usize, usize),
+    kind: ErrorKind,
+    suggestion: Option<Suggestion>,
+}
+
+impl<'a> Error<'a> {
+    /// The format string which failed to parse.
+    #[inline]
+    pub fn input(&self) -> &str {
+        self.input.as_str()
+    }
+
+    /// Byte range of the offending part of the input.
+    ///
+    /// The range is empty when something is missing at the end of the input.
+    #[inline]
+    pub fn span(&self) -> Range<usize> {
+        self.span.0..self.span.1
+    }
+
+    /// The way in which the input departs from the grammar.
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// A correction of the [span][Error::span], if one can be guessed.
+    #[inline]
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        self.suggestion
+    }
+
+    /// The input with the [suggestion][Error::suggestion] applied.
+    ///
+    /// ```rust
+    /// # use num_runtime_fmt::NumFmt;
+    /// let err = NumFmt::from_str(",x").unwrap_err();
+    /// assert_eq!(err.corrected().as_deref(), Some("x,"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn corrected(&self) -> Option<String> {
+        let mut corrected = String::new();
+        self.write_corrected(&mut corrected)
+            .expect("writing to a String never fails")?;
+        Some(corrected)
+    }
+
+    /// Detach this error from the lifetime of the input, by copying it.
+    #[cfg(feature = "alloc")]
+    pub fn into_owned(self) -> Error<'static> {
+        Error {
+            input: Input::Owned(self.input.as_str().into()),
+            span: self.span,
+            kind: self.kind,
+            suggestion: self.suggestion,
+        }
+    }
+
+    /// Write the input with the suggestion applied, if there is one.
+    fn write_corrected(&self, out: &mut impl fmt::Write) -> Result<Option<()>, fmt::Error> {
+        let input = self.input();
+        let (start, end) = self.span;
+        match self.suggestion {
+            None => return Ok(None),
+            Some(Suggestion::Remove) => {
+                out.write_str(&input[..start])?;
+                out.write_str(&input[end..])?;
+            }
+            Some(Suggestion::Replace(replacement)) => {
+                out.write_str(&input[..start])?;
+                out.write_str(replacement)?;
+                out.write_str(&input[end..])?;
+            }
+            Some(Suggestion::Move(to)) => {
+                out.write_str(&input[..to])?;
+                out.write_str(&input[start..end])?;
+                out.write_str(&input[to..start])?;
+                out.write_str(&input[end..])?;
+            }
+        }
+        Ok(Some(()))
+    }
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let input = self.input();
+        let (start, end) = self.span;
+        // align the carets with the input as displayed in a terminal
+        let columns = WidthUnit::Columns;
+        let indent = columns.str_width(&input[..start]);
+        let carets = columns.str_width(&input[start..end]).max(1);
+
+        writeln!(f, "{}", self.kind)?;
+        writeln!(f, "  {}", input)?;
+        write!(f, "  {:indent$}", "", indent = indent)?;
+        for _ in 0..carets {
+            f.write_str("^")?;
+        }
+        if self.suggestion.is_some() {
+            f.write_str("\nhelp: did you mean `")?;
+            self.write_corrected(f)?;
+            f.write_str("`?")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error<'_> {}
+
+/// Consume a run of ASCII digits with [`Parser::number`], returning early if it is too large.
+///
+/// This does the job of `?`, which is not available in a `const fn`.
+macro_rules! number {
+    ($parser:expr, $component:expr, $expected:expr) => {
+        match $parser.number($component, $expected) {
+            Ok(value) => value,
+            Err(span) => return Err($parser.error(span, ErrorKind::TooLarge, None)),
+        }
+    };
+}
+
+/// Cursor over the bytes of a format string.
+struct Parser<'a> {
+    input: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    /// Earliest component which could appear at `pos`.
+    expected: Expected,
+    /// Position at which each component was sought.
+    starts: [Option<usize>; COMPONENTS],
+    /// Whether each component was found.
+    found: [bool; COMPONENTS],
+}
+
+impl<'a> Parser<'a> {
+    const fn new(input: &'a str) -> Self {
+        Parser {
+            input,
+            bytes: input.as_bytes(),
+            pos: 0,
+            expected: Expected::Align,
+            starts: [None; COMPONENTS],
+            found: [false; COMPONENTS],
+        }
+    }
+
+    #[inline]
+    const fn byte_at(&self, pos: usize) -> Option<u8> {
+        if pos < self.bytes.len() {
+            Some(self.bytes[pos])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    const fn peek(&self) -> Option<u8> {
+        self.byte_at(self.pos)
+    }
+
+    /// Decode the `char` at the cursor, and its length in bytes.
+    const fn peek_char(&self) -> Option<(char, usize)> {
+        let first = match self.peek() {
+            Some(first) => first as u32,
+            None => return None,
+        };
+        let (len, mut code) = match first {
+            0x00..=0x7f => (1, first),
+            0xc0..=0xdf => (2, first & 0x1f),
+            0xe0..=0xef => (3, first & 0x0f),
+            _ => (4, first & 0x07),
+        };
+        let mut idx = 1;
+        while idx < len {
+            code = (code << 6) | (self.bytes[self.pos + idx] as u32 & 0x3f);
+            idx += 1;
+        }
+        match char::from_u32(code) {
+            Some(ch) => Some((ch, len)),
+            None => panic!("a `str` is always valid UTF-8"),
+        }
+    }
+
+    /// `true` when the input at the cursor starts with `token`.
+    const fn starts_with(&self, token: &str) -> bool {
+        let token = token.as_bytes();
+        if self.bytes.len() - self.pos < token.len() {
+            return false;
+        }
+        let mut idx = 0;
+        while idx < token.len() {
+            if self.bytes[self.pos + idx] != token[idx] {
+                return false;
+            }
+            idx += 1;
+        }
+        true
+    }
+
+    /// `true` when nothing but ASCII digits follow the byte at the cursor.
+    const fn only_digits_follow(&self) -> bool {
+        self.digits_end(self.pos + 1) == self.bytes.len()
+    }
+
+    /// Position following the run of ASCII digits starting at `pos`.
+    const fn digits_end(&self, mut pos: usize) -> usize {
+        while pos < self.bytes.len() && self.bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        pos
+    }
+
+    /// Note that `component` may appear at the cursor.
+    #[inline]
+    const fn seek(&mut self, component: Component) {
+        self.starts[component as usize] = Some(self.pos);
+    }
+
+    /// Consume `len` bytes of `component`, after which the `expected` component can appear.
+    #[inline]
+    const fn take(&mut self, component: Component, len: usize, expected: Expected) {
+        self.found[component as usize] = true;
+        self.pos += len;
+        self.expected = expected;
+    }
+
+    /// Consume a run of ASCII digits of `component`, after which the `expected` component can
+    /// appear.
+    ///
+    /// An empty run has the value 0. Fails with the span of the run if it does not fit in a
+    /// `usize`.
+    const fn number(
+        &mut self,
+        component: Component,
+        expected: Expected,
+    ) -> Result<usize, (usize, usize)> {
+        let span = (self.pos, self.digits_end(self.pos));
+        let mut value: usize = 0;
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            value = match value.checked_mul(10) {
+                Some(value) => match value.checked_add((digit - b'0') as usize) {
+                    Some(value) => value,
+                    None => return Err(span),
+                },
+                None => return Err(span),
+            };
+            self.take(component, 1, expected);
+        }
+        Ok(value)
+    }
+
+    /// The component which a token at the cursor would belong to, and the length of the token.
+    const fn token(&self) -> Option<(Component, usize)> {
+        let pos = self.pos;
+        if self.starts_with("±") {
+            return Some((Component::ZeroSign, '±'.len_utf8()));
+        }
+        if self.starts_with("‰") {
+            return Some((Component::Format, '‰'.len_utf8()));
+        }
+        if self.starts_with("bp") {
+            return Some((Component::Format, 2));
+        }
+        match self.peek() {
+            Some(b'<' | b'^' | b'>' | b'v' | b'=') => Some((Component::Align, 1)),
+            Some(b'-' | b'+' | b'(' | b't' | b'c') => Some((Component::Sign, 1)),
+            Some(b'z') => Some((Component::ZeroSign, 1)),
+            Some(b'#') => Some((Component::Hash, 1)),
+            Some(b'0'..=b'9') => Some((Component::Width, self.digits_end(pos) - pos)),
+            Some(b'.') => Some((Component::Precision, self.digits_end(pos + 1) - pos)),
+            Some(b'b' | b'o' | b'd' | b'x' | b'X' | b'e' | b'E' | b'%') => {
+                Some((Component::Format, 1))
+            }
+            Some(b'_' | b',' | b' ') => {
+                Some((Component::Separator, self.digits_end(pos + 1) - pos))
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    const fn error(
+        &self,
+        span: (usize, usize),
+        kind: ErrorKind,
+        suggestion: Option<Suggestion>,
+    ) -> Error<'a> {
+        Error {
+            input: Input::Borrowed(self.input),
+            span,
+            kind,
+            suggestion,
+        }
+    }
+
+    /// Diagnose the token at the cursor, which the grammar does not permit there.
+    const fn unexpected(&self) -> Error<'a> {
+        let start = self.pos;
+        let (found, len) = match self.peek_char() {
+            Some(found) => found,
+            None => panic!("the end of the input is always permitted"),
+        };
+        if let Some((component, len)) = self.token() {
+            let span = (start, start + len);
+            if self.found[component as usize] {
+                return self.error(
+                    span,
+                    ErrorKind::Duplicate(component),
+                    Some(Suggestion::Remove),
+                );
+            }
+            // every component has been sought by the time the parser gives up
+            if let Some(to) = self.starts[component as usize] {
+                return self.error(
+                    span,
+                    ErrorKind::Misplaced(component),
+                    Some(Suggestion::Move(to)),
+                );
+            }
+        }
+
+        let span = (start, start + len);
+        if found.is_ascii_alphabetic() {
+            let suggestion = match found {
+                // positional notation is the default
+                'f' | 'F' | 'g' | 'G' => Some(Suggestion::Remove),
+                'B' => Some(Suggestion::Replace("b")),
+                'O' => Some(Suggestion::Replace("o")),
+                'D' | 'i' | 'u' | 'n' => Some(Suggestion::Replace("d")),
+                _ => None,
+            };
+            return self.error(span, ErrorKind::UnknownFormat(found), suggestion);
+        }
+        self.error(
+            span,
+            ErrorKind::Unexpected {
+                found,
+                expected: self.expected,
+            },
+            None,
+        )
+    }
+}
+
+const fn align_of(byte: Option<u8>) -> Option<Align> {
+    match byte {
+        Some(b'<') => Some(Align::Left),
+        Some(b'^') => Some(Align::Center),
+        Some(b'>') => Some(Align::Right),
+        Some(b'v') => Some(Align::Decimal),
+        Some(b'=') => Some(Align::AfterSign),
+        _ => None,
+    }
 }
 
 /// Parse a `NumFmt` instance from a format string.
 ///
 /// See crate-level docs for the grammar.
-pub(crate) fn parse(s: &str) -> Result<NumFmt, Error> {
-    let captures = PARSE_RE.captures(s).ok_or(Error::NoMatch)?;
-    let str_of = |name: &str| captures.name(name).map(|m| m.as_str());
-    let char_of = |name: &str| str_of(name).and_then(|s| s.chars().next());
-
-    let mut builder = NumFmt::builder();
-
-    if let Some(fill) = char_of("fill") {
-        builder = builder.fill(fill);
-    }
-    if let Some(align) = char_of("align") {
-        builder = builder.align(match align {
-            '<' => Align::Left,
-            '^' => Align::Center,
-            '>' => Align::Right,
-            'v' => Align::Decimal,
-            _ => unreachable!("guaranteed by regex"),
-        });
-    }
-    if let Some(sign) = char_of("sign") {
-        builder = builder.sign(match sign {
-            '-' => Sign::OnlyMinus,
-            '+' => Sign::PlusAndMinus,
-            _ => unreachable!("guaranteed by regex"),
-        });
-    }
-    if char_of("hash").is_some() {
-        builder = builder.hash(true);
-    }
-    if char_of("zero").is_some() {
-        builder = builder.zero(true);
-    }
-    if let Some(width) = str_of("width") {
-        let width = width
-            .parse()
-            .map_err(|err| Error::ParseInt(width.to_string(), err))?;
-        builder = builder.width(width);
-    }
-    if let Some(precision) = str_of("precision") {
-        let precision = precision
-            .parse()
-            .map_err(|err| Error::ParseInt(precision.to_string(), err))?;
-        builder = builder.precision(Some(precision));
-    }
-    if let Some(format) = char_of("format") {
-        builder = builder.base(match format {
-            'b' => Base::Binary,
-            'o' => Base::Octal,
-            'd' => Base::Decimal,
-            'x' => Base::LowerHex,
-            'X' => Base::UpperHex,
-            _ => unreachable!("guaranteed by regex"),
-        });
-    }
-    builder = builder.separator(char_of("separator"));
-    if let Some(spacing) = str_of("spacing") {
-        let spacing = spacing
-            .parse()
-            .map_err(|err| Error::ParseInt(spacing.to_string(), err))?;
-        builder = builder.spacing(spacing);
-    }
-
-    Ok(builder.build())
+pub(crate) const fn parse(s: &str) -> Result<NumFmt, Error<'_>> {
+    let mut parser = Parser::new(s);
+
+    // No component after the alignment starts with an align char, so an align char in second
+    // place is always preceded by a fill. Any char but a newline can be the fill.
+    parser.seek(Component::Align);
+    let mut fill = None;
+    let mut align = Align::Right;
+    if let Some((ch, len)) = parser.peek_char() {
+        let after_fill = match ch {
+            '\n' => None,
+            _ => align_of(parser.byte_at(parser.pos + len)),
+        };
+        if let Some(after_fill) = after_fill {
+            fill = Some(ch);
+            align = after_fill;
+            parser.take(Component::Align, len + 1, Expected::Sign);
+        } else if let Some(first) = align_of(parser.peek()) {
+            align = first;
+            parser.take(Component::Align, 1, Expected::Sign);
+        }
+    }
+
+    parser.seek(Component::Sign);
+    let sign = match parser.peek() {
+        Some(b'-') => Some(Sign::OnlyMinus),
+        Some(b'+') => Some(Sign::PlusAndMinus),
+        Some(b'(') => Some(Sign::Parentheses),
+        Some(b't') => Some(Sign::TrailingMinus),
+        Some(b'c') => Some(Sign::CreditDebit),
+        // a space is only a sign when it can't be a separator
+        Some(b' ') if !parser.only_digits_follow() => Some(Sign::SpaceAndMinus),
+        _ => None,
+    };
+    if sign.is_some() {
+        parser.take(Component::Sign, 1, Expected::ZeroSign);
+    }
+
+    parser.seek(Component::ZeroSign);
+    let mut zero_sign = ZeroSign::Natural;
+    if parser.starts_with("z") {
+        zero_sign = ZeroSign::Positive;
+        parser.take(Component::ZeroSign, 1, Expected::Hash);
+    } else if parser.starts_with("±") {
+        zero_sign = ZeroSign::PlusMinus;
+        parser.take(Component::ZeroSign, '±'.len_utf8(), Expected::Hash);
+    }
+
+    parser.seek(Component::Hash);
+    let hash = parser.starts_with("#");
+    if hash {
+        parser.take(Component::Hash, 1, Expected::Width);
+    }
+
+    parser.seek(Component::Width);
+    let zero = parser.starts_with("0");
+    if zero {
+        parser.take(Component::Width, 1, Expected::Precision);
+    }
+    let mut width = 0;
+    match parser.peek() {
+        Some(b'1'..=b'9') => {
+            width = number!(parser, Component::Width, Expected::Precision);
+        }
+        Some(b'0') if zero => {
+            let start = parser.pos;
+            let mut end = start;
+            while matches!(parser.byte_at(end), Some(b'0')) {
+                end += 1;
+            }
+            let suggestion = match parser.byte_at(end) {
+                Some(b'1'..=b'9') => Some(Suggestion::Remove),
+                _ => None,
+            };
+            return Err(parser.error((start, end), ErrorKind::LeadingZero, suggestion));
+        }
+        _ if zero => {
+            let flag = (parser.pos - 1, parser.pos);
+            return Err(parser.error(flag, ErrorKind::MissingWidth, None));
+        }
+        _ => {}
+    }
+
+    parser.seek(Component::Precision);
+    let mut precision = None;
+    if parser.starts_with(".") {
+        parser.take(Component::Precision, 1, Expected::Format);
+        if !matches!(parser.peek(), Some(b'0'..=b'9')) {
+            let dot = (parser.pos - 1, parser.pos);
+            return Err(parser.error(dot, ErrorKind::MissingPrecision, Some(Suggestion::Remove)));
+        }
+        precision = Some(number!(parser, Component::Precision, Expected::Format));
+    }
+
+    parser.seek(Component::Format);
+    let mut base = Base::Decimal;
+    let mut notation = Notation::Positional;
+    let mut exponent_style = ExponentStyle::LowerE;
+    let mut ratio = None;
+    let format_len = if parser.starts_with("bp") {
+        ratio = Some(Ratio::BasisPoint);
+        2
+    } else if parser.starts_with("‰") {
+        ratio = Some(Ratio::PerMille);
+        '‰'.len_utf8()
+    } else {
+        match parser.peek() {
+            Some(b'b') => base = Base::Binary,
+            Some(b'o') => base = Base::Octal,
+            Some(b'd') => base = Base::Decimal,
+            Some(b'x') => base = Base::LowerHex,
+            Some(b'X') => base = Base::UpperHex,
+            Some(b'e') => notation = Notation::Scientific,
+            Some(b'E') => {
+                notation = Notation::Scientific;
+                exponent_style = ExponentStyle::UpperE;
+            }
+            Some(b'%') => ratio = Some(Ratio::Percent),
+            _ => {}
+        }
+        match parser.peek() {
+            Some(b'b' | b'o' | b'd' | b'x' | b'X' | b'e' | b'E' | b'%') => 1,
+            _ => 0,
+        }
+    };
+    if format_len > 0 {
+        parser.take(Component::Format, format_len, Expected::Separator);
+    }
+
+    parser.seek(Component::Separator);
+    let separator = match parser.peek() {
+        Some(separator @ (b'_' | b',' | b' ')) => Some(separator as char),
+        _ => None,
+    };
+    let mut spacing = None;
+    if separator.is_some() {
+        parser.take(Component::Separator, 1, Expected::Spacing);
+        if matches!(parser.peek(), Some(b'0'..=b'9')) {
+            let start = parser.pos;
+            let value = number!(parser, Component::Separator, Expected::End);
+            if value == 0 {
+                let digits = (start, parser.pos);
+                return Err(parser.error(digits, ErrorKind::ZeroSpacing, Some(Suggestion::Remove)));
+            }
+            spacing = Some(value);
+        }
+    }
+
+    if parser.peek().is_some() {
+        return Err(parser.unexpected());
+    }
+
+    // the zero flag overrides the fill
+    let fill = match (zero, fill) {
+        (true, _) => Some(Text::from_char('0')),
+        (false, Some(fill)) => Some(Text::from_char(fill)),
+        (false, None) => None,
+    };
+    let sign = match sign {
+        Some(sign) => sign,
+        None => Sign::OnlyMinus,
+    };
+
+    Ok(NumFmt {
+        fill,
+        right_fill: None,
+        align,
+        sign,
+        zero_sign,
+        minus_sign: None,
+        plus_sign: None,
+        hash,
+        base_marker: BaseMarker::Rust,
+        zero,
+        width,
+        width_unit: WidthUnit::Chars,
+        max_width: None,
+        overflow: Overflow::Extend,
+        min_integer_digits: None,
+        max_integer_digits: None,
+        precision,
+        min_fraction_digits: None,
+        max_fraction_digits: None,
+        force_decimal_point: false,
+        base,
+        notation,
+        exponent_style,
+        separator,
+        spacing,
+        decimal_separator: None,
+        ratio,
+        scale: 0,
+        currency: None,
+        currency_display: CurrencyDisplay::Symbol,
+        prefix: None,
+        suffix: None,
+    })
 }
 
 #[cfg(test)]
@@ -111,7 +758,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_re_matches() {
+    fn test_parse_matches() {
         for format_str in &[
             "",
             "<",
@@ -121,12 +768,218 @@ mod tests {
             "v-10.2",
             "#04x_2",
             "-v-#012.3d 4",
+            ".1%",
+            "+08.2‰",
+            "bp_",
+            "(10.2,",
+            ">t8",
+            "c.2",
+            " 5d",
+            "=+8",
+            "*=#010x",
+            " .2",
+            "+z.1",
+            "±",
+            ".3e",
+            "+12E_",
+            "🖤^8",
         ] {
             println!("{:?}:", format_str);
             assert!(
-                PARSE_RE.captures(format_str).is_some(),
+                parse(format_str).is_ok(),
                 "all valid format strings must be parsed"
             );
         }
     }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(parse("").unwrap(), NumFmt::default());
+    }
+
+    #[test]
+    fn test_space_sign_or_separator() {
+        let sign_of = |s: &str| parse(s).unwrap().sign();
+        let separator_of = |s: &str| parse(s).unwrap().separator();
+
+        assert_eq!(separator_of(" "), Some(' '));
+        assert_eq!(sign_of(" "), Sign::OnlyMinus);
+        assert_eq!(separator_of(" 2"), Some(' '));
+        assert_eq!(sign_of(" 2"), Sign::OnlyMinus);
+
+        assert_eq!(sign_of(" 2d"), Sign::SpaceAndMinus);
+        assert_eq!(separator_of(" 2d"), None);
+        assert_eq!(sign_of(" .2"), Sign::SpaceAndMinus);
+        assert_eq!(sign_of(" 5,"), Sign::SpaceAndMinus);
+        assert_eq!(separator_of(" 5,"), Some(','));
+        assert_eq!(sign_of(" 5 "), Sign::SpaceAndMinus);
+        assert_eq!(separator_of(" 5 "), Some(' '));
+    }
+
+    #[test]
+    fn test_errors() {
+        let diagnosis = |s| {
+            let err = parse(s).unwrap_err();
+            (err.span(), err.kind(), err.suggestion())
+        };
+        let unexpected = |found, expected| ErrorKind::Unexpected { found, expected };
+
+        assert_eq!(
+            diagnosis("*"),
+            (0..1, unexpected('*', Expected::Align), None)
+        );
+        assert_eq!(
+            diagnosis(">*"),
+            (1..2, unexpected('*', Expected::Sign), None)
+        );
+        assert_eq!(
+            diagnosis("+#🖤"),
+            (2..6, unexpected('🖤', Expected::Width), None)
+        );
+        assert_eq!(
+            diagnosis("\n<"),
+            (0..1, unexpected('\n', Expected::Align), None)
+        );
+        assert_eq!(
+            diagnosis("_3*"),
+            (2..3, unexpected('*', Expected::End), None)
+        );
+        assert_eq!(diagnosis("0"), (0..1, ErrorKind::MissingWidth, None));
+        assert_eq!(diagnosis("0x"), (0..1, ErrorKind::MissingWidth, None));
+        assert_eq!(diagnosis("00"), (1..2, ErrorKind::LeadingZero, None));
+        assert_eq!(
+            diagnosis("+0005"),
+            (2..4, ErrorKind::LeadingZero, Some(Suggestion::Remove))
+        );
+        assert_eq!(
+            diagnosis("8.x"),
+            (1..2, ErrorKind::MissingPrecision, Some(Suggestion::Remove))
+        );
+        assert_eq!(
+            diagnosis("8.2f"),
+            (
+                3..4,
+                ErrorKind::UnknownFormat('f'),
+                Some(Suggestion::Remove)
+            )
+        );
+        assert_eq!(
+            diagnosis("#B"),
+            (
+                1..2,
+                ErrorKind::UnknownFormat('B'),
+                Some(Suggestion::Replace("b"))
+            )
+        );
+        assert_eq!(diagnosis("k"), (0..1, ErrorKind::UnknownFormat('k'), None));
+        assert_eq!(
+            diagnosis("xx"),
+            (
+                1..2,
+                ErrorKind::Duplicate(Component::Format),
+                Some(Suggestion::Remove)
+            )
+        );
+        assert_eq!(
+            diagnosis("_4,4"),
+            (
+                2..4,
+                ErrorKind::Duplicate(Component::Separator),
+                Some(Suggestion::Remove)
+            )
+        );
+        assert_eq!(
+            diagnosis(",x"),
+            (
+                1..2,
+                ErrorKind::Misplaced(Component::Format),
+                Some(Suggestion::Move(0))
+            )
+        );
+        assert_eq!(
+            diagnosis("#x+"),
+            (
+                2..3,
+                ErrorKind::Misplaced(Component::Sign),
+                Some(Suggestion::Move(0))
+            )
+        );
+        assert_eq!(
+            diagnosis(">#x.25"),
+            (
+                3..6,
+                ErrorKind::Misplaced(Component::Precision),
+                Some(Suggestion::Move(2))
+            )
+        );
+        assert_eq!(
+            diagnosis(",0"),
+            (1..2, ErrorKind::ZeroSpacing, Some(Suggestion::Remove))
+        );
+        assert_eq!(
+            diagnosis("x_00"),
+            (2..4, ErrorKind::ZeroSpacing, Some(Suggestion::Remove))
+        );
+        assert_eq!(diagnosis("±k"), (2..3, ErrorKind::UnknownFormat('k'), None));
+        assert_eq!(
+            diagnosis("99999999999999999999999"),
+            (0..23, ErrorKind::TooLarge, None)
+        );
+        assert_eq!(
+            diagnosis(".9_99999999999999999999999"),
+            (3..26, ErrorKind::TooLarge, None)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_corrected() {
+        let corrected = |s| parse(s).unwrap_err().corrected();
+
+        assert_eq!(corrected("+0005").as_deref(), Some("+05"));
+        assert_eq!(corrected("8.x").as_deref(), Some("8x"));
+        assert_eq!(corrected("#B").as_deref(), Some("#b"));
+        assert_eq!(corrected("_4,4").as_deref(), Some("_4"));
+        assert_eq!(corrected("x_00").as_deref(), Some("x_"));
+        assert_eq!(corrected("#x+").as_deref(), Some("+#x"));
+        assert_eq!(corrected(">#x.25").as_deref(), Some(">#.25x"));
+        assert_eq!(corrected("0x"), None);
+        for s in [
+            "+0005", "8.x", "#B", "_4,4", "#x+", ">#x.25", ",x", "xx", "8.2f",
+        ] {
+            assert!(parse(&corrected(s).unwrap()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            parse("+#🖤").unwrap_err().to_string(),
+            "unexpected `🖤`: expected a width, or any later component\n  +#🖤\n    ^^",
+        );
+        assert_eq!(
+            parse("🖤>8.").unwrap_err().to_string(),
+            "`.` must be followed by the precision\n  🖤>8.\n      ^\nhelp: did you mean `🖤>8`?",
+        );
+    }
+
+    #[test]
+    fn test_const() {
+        const FMT: Result<NumFmt, Error<'static>> = parse("*^+#010.2x_4");
+        assert_eq!(
+            FMT.unwrap(),
+            NumFmt::builder()
+                .fill('*')
+                .align(Align::Center)
+                .sign(Sign::PlusAndMinus)
+                .hash(true)
+                .zero(true)
+                .width(10)
+                .precision(Some(2))
+                .base(Base::LowerHex)
+                .separator(Some('_'))
+                .spacing(4)
+                .build()
+        );
+    }
 }
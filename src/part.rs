@@ -0,0 +1,260 @@
+#[cfg(feature = "std")]
+use crate::Error;
+use crate::WidthUnit;
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+/// The role of a [`Part`] of a formatted number.
+///
+/// - `Padding`: fill added to reach the `width`
+/// - `Sign`: a sign, whether before or after the number, i.e. `-`, `(`, `)` or ` CR`
+/// - `Prefix`: a literal prefix or a base specification preceding the digits, i.e. `0x`
+/// - `Currency`: a currency marker, including any space separating it from the number
+/// - `Integer`: a group of digits before the decimal, including leading zeros
+/// - `GroupSeparator`: the separator between groups of integer digits
+/// - `DecimalSeparator`: the decimal point
+/// - `Fraction`: the digits after the decimal
+/// - `Exponent`: the exponent of scientific notation, i.e. `e-4`
+/// - `Suffix`: a literal suffix, a base specification following the digits, or a ratio symbol
+/// - `Overflow`: the `#` fill or truncation ellipsis of a number wider than the maximum width
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum PartKind {
+    Padding,
+    Sign,
+    Prefix,
+    Currency,
+    Integer,
+    GroupSeparator,
+    DecimalSeparator,
+    Fraction,
+    Exponent,
+    Suffix,
+    Overflow,
+}
+
+impl PartKind {
+    /// Name of this kind of part in kebab case, i.e. `"group-separator"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Padding => "padding",
+            Self::Sign => "sign",
+            Self::Prefix => "prefix",
+            Self::Currency => "currency",
+            Self::Integer => "integer",
+            Self::GroupSeparator => "group-separator",
+            Self::DecimalSeparator => "decimal-separator",
+            Self::Fraction => "fraction",
+            Self::Exponent => "exponent",
+            Self::Suffix => "suffix",
+            Self::Overflow => "overflow",
+        }
+    }
+}
+
+/// A span of a formatted number. See [`NumFmt::fmt_to_parts`][crate::NumFmt::fmt_to_parts].
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Part {
+    pub kind: PartKind,
+    pub text: String,
+}
+
+#[cfg(feature = "alloc")]
+impl Part {
+    /// Construct a `Part`.
+    pub fn new(kind: PartKind, text: impl Into<String>) -> Part {
+        Part {
+            kind,
+            text: text.into(),
+        }
+    }
+}
+
+/// Destination of a rendered number.
+pub(crate) trait Sink {
+    /// Append `text` of the given kind.
+    fn push_str(&mut self, kind: PartKind, text: &str);
+
+    /// Append a `char` of the given kind.
+    fn push(&mut self, kind: PartKind, ch: char) {
+        self.push_str(kind, ch.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Reserve capacity for at least `additional` more bytes.
+    fn reserve(&mut self, _additional: usize) {}
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for String {
+    #[inline]
+    fn push_str(&mut self, _kind: PartKind, text: &str) {
+        String::push_str(self, text);
+    }
+
+    #[inline]
+    fn push(&mut self, _kind: PartKind, ch: char) {
+        String::push(self, ch);
+    }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        String::reserve(self, additional);
+    }
+}
+
+/// Adjacent spans of the same kind are merged into a single part.
+#[cfg(feature = "alloc")]
+impl Sink for Vec<Part> {
+    fn push_str(&mut self, kind: PartKind, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        match self.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(text),
+            _ => Vec::push(self, Part::new(kind, text)),
+        }
+    }
+}
+
+/// Sink which discards its input; useful to measure the width of a rendered number.
+pub(crate) struct Discard;
+
+impl Sink for Discard {
+    #[inline]
+    fn push_str(&mut self, _kind: PartKind, _text: &str) {}
+
+    #[inline]
+    fn push(&mut self, _kind: PartKind, _ch: char) {}
+}
+
+/// Sink streaming into a [`fmt::Write`].
+///
+/// The first error is retained, and all input following it is discarded.
+pub(crate) struct FmtSink<'a, W: ?Sized> {
+    pub(crate) writer: &'a mut W,
+    pub(crate) result: fmt::Result,
+}
+
+impl<'a, W: fmt::Write + ?Sized> FmtSink<'a, W> {
+    pub(crate) fn new(writer: &'a mut W) -> Self {
+        FmtSink {
+            writer,
+            result: Ok(()),
+        }
+    }
+}
+
+impl<W: fmt::Write + ?Sized> Sink for FmtSink<'_, W> {
+    #[inline]
+    fn push_str(&mut self, _kind: PartKind, text: &str) {
+        if self.result.is_ok() {
+            self.result = self.writer.write_str(text);
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, _kind: PartKind, ch: char) {
+        if self.result.is_ok() {
+            self.result = self.writer.write_char(ch);
+        }
+    }
+}
+
+/// Adapter which streams a [`fmt::Write`] into an [`io::Write`].
+///
+/// The first I/O error is retained, and reported to the formatter as a [`fmt::Error`].
+#[cfg(feature = "std")]
+pub(crate) struct IoAdapter<'a, W: ?Sized> {
+    pub(crate) inner: &'a mut W,
+    pub(crate) error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: io::Write + ?Sized> IoAdapter<'a, W> {
+    pub(crate) fn new(inner: &'a mut W) -> Self {
+        IoAdapter { inner, error: None }
+    }
+
+    /// Convert the result of formatting into this adapter into an I/O result.
+    ///
+    /// Formatting errors are reported as [`io::ErrorKind::InvalidInput`].
+    pub(crate) fn finish(self, result: Result<(), Error>) -> io::Result<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(Error::Write) => Err(self.error.unwrap_or_else(|| io::Error::other(Error::Write))),
+            Err(err) => Err(io::Error::new(io::ErrorKind::InvalidInput, err)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write + ?Sized> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Wrapper around a sink which measures the width of its input.
+///
+/// If a `limit` is set, input is discarded from the first `char` which would exceed it.
+pub(crate) struct Measured<'a, S: ?Sized> {
+    sink: &'a mut S,
+    unit: WidthUnit,
+    width: usize,
+    limit: Option<usize>,
+    full: bool,
+}
+
+impl<'a, S: Sink + ?Sized> Measured<'a, S> {
+    pub(crate) fn new(sink: &'a mut S, unit: WidthUnit, limit: Option<usize>) -> Self {
+        Measured {
+            sink,
+            unit,
+            width: 0,
+            limit,
+            full: false,
+        }
+    }
+
+    /// Width of the input so far, in the configured unit.
+    #[inline]
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for Measured<'_, S> {
+    fn push_str(&mut self, kind: PartKind, text: &str) {
+        if self.limit.is_none() {
+            self.width += self.unit.str_width(text);
+            self.sink.push_str(kind, text);
+            return;
+        }
+        for ch in text.chars() {
+            self.push(kind, ch);
+        }
+    }
+
+    fn push(&mut self, kind: PartKind, ch: char) {
+        let ch_width = self.unit.char_width(ch);
+        if let Some(limit) = self.limit {
+            self.full |= self.width + ch_width > limit;
+            if self.full {
+                return;
+            }
+        }
+        self.width += ch_width;
+        self.sink.push(kind, ch);
+    }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        self.sink.reserve(additional);
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(feature = "std")]
+use crate::part::IoAdapter;
+use crate::{num_fmt::Resolved, part::FmtSink, Dynamic, Error, NumFmt, Numeric};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{fmt, marker::PhantomData};
+#[cfg(feature = "std")]
+use std::io;
+
+/// A [`NumFmt`] compiled for formatting numbers of type `N` with particular [`Dynamic`]
+/// parameters. Created by [`NumFmt::compile`].
+///
+/// Everything which does not depend on the number being formatted, such as the signs, the base
+/// and currency markers, the width left for the digits, the group separator and the count of
+/// digits after the decimal, is resolved once, when the plan is compiled. Writing with a plan
+/// does not allocate, which makes it the fastest way to format the same column of a table many
+/// times.
+///
+/// ```rust
+/// # use num_runtime_fmt::{Dynamic, NumFmt};
+/// let fmt = NumFmt::from_str(">10.2,").unwrap();
+/// let plan = fmt.compile::<f64>(Dynamic::default()).unwrap();
+/// let mut column = String::new();
+/// for number in [1234.5, -0.25] {
+///     plan.write_to(&mut column, number).unwrap();
+///     column.push('\n');
+/// }
+/// assert_eq!(column, "  1,234.50\n     -0.25\n");
+/// ```
+///
+/// A plan produces exactly the same output as [`NumFmt::fmt_with`].
+#[derive(Clone, Debug)]
+pub struct Plan<N> {
+    fmt: NumFmt,
+    dynamic: Dynamic,
+    resolved: Resolved,
+    number: PhantomData<fn(N)>,
+}
+
+impl<N: Numeric> Plan<N> {
+    pub(crate) fn new(fmt: &NumFmt, dynamic: Dynamic) -> Result<Self, Error> {
+        Ok(Plan {
+            fmt: fmt.clone(),
+            dynamic,
+            resolved: Resolved::new(fmt, dynamic)?,
+            number: PhantomData,
+        })
+    }
+
+    /// Format the provided number according to this plan.
+    ///
+    /// See [`NumFmt::fmt`].
+    #[cfg(feature = "alloc")]
+    pub fn fmt(&self, number: N) -> Result<String, Error> {
+        let mut rendered = String::new();
+        self.fmt
+            .fmt_resolved(&self.resolved, &number, &mut rendered)?;
+        Ok(rendered)
+    }
+
+    /// Format the provided number according to this plan into a writer.
+    ///
+    /// See [`NumFmt::write_to`].
+    pub fn write_to<W: fmt::Write + ?Sized>(&self, writer: &mut W, number: N) -> Result<(), Error> {
+        let mut sink = FmtSink::new(writer);
+        self.fmt.fmt_resolved(&self.resolved, &number, &mut sink)?;
+        sink.result.map_err(|_| Error::Write)
+    }
+
+    /// Format the provided number according to this plan into an I/O stream.
+    ///
+    /// See [`NumFmt::write_io`].
+    #[cfg(feature = "std")]
+    pub fn write_io<W: io::Write + ?Sized>(&self, writer: &mut W, number: N) -> io::Result<()> {
+        let mut adapter = IoAdapter::new(writer);
+        let result = self.write_to(&mut adapter, number);
+        adapter.finish(result)
+    }
+
+    /// The configuration from which this plan was compiled.
+    #[inline]
+    pub fn num_fmt(&self) -> &NumFmt {
+        &self.fmt
+    }
+
+    /// The dynamic parameters with which this plan was compiled.
+    #[inline]
+    pub fn dynamic(&self) -> Dynamic {
+        self.dynamic
+    }
+}
@@ -0,0 +1,38 @@
+/// Render a number as a proportion of some whole.
+///
+/// Each ratio scales the number by a power of ten and appends its symbol:
+///
+/// - `Percent`: multiply by 100 and append `%`
+/// - `PerMille`: multiply by 1000 and append `‰`
+/// - `BasisPoint`: multiply by 10000 and append `bp`
+///
+/// Scaling is performed on the decimal digits of the number, not by arithmetic, so it never
+/// introduces floating-point error.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ratio {
+    Percent,
+    PerMille,
+    BasisPoint,
+}
+
+impl Ratio {
+    /// The power of ten by which this ratio scales the number.
+    #[inline]
+    pub fn exponent(self) -> i32 {
+        match self {
+            Self::Percent => 2,
+            Self::PerMille => 3,
+            Self::BasisPoint => 4,
+        }
+    }
+
+    /// The symbol appended to the number.
+    #[inline]
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Percent => "%",
+            Self::PerMille => "‰",
+            Self::BasisPoint => "bp",
+        }
+    }
+}
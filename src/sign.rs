@@ -3,10 +3,25 @@
 /// - `OnlyMinus`: print a leading `-` for negative numbers, and nothing in particular for
 ///   positive (default)
 /// - `PlusAndMinus`: print a leading `+` for positive numbers
+/// - `SpaceAndMinus`: print a leading space for positive numbers, so that they line up in
+///   columns with negative numbers
+/// - `Parentheses`: enclose negative numbers in parentheses, i.e. `(1,234.00)`. Positive numbers
+///   are followed by a space, so that the closing parenthesis lines up in columns.
+/// - `TrailingMinus`: print a trailing `-` for negative numbers, i.e. `1234-`. Positive numbers
+///   are followed by a space.
+/// - `CreditDebit`: print a trailing ` CR` for negative numbers. Only credits are marked:
+///   positive numbers are followed by three spaces.
+///
+/// Trailing signs count toward the `width` of the number. With `Align::Decimal`, they are
+/// rendered after the decimal, so they do not count toward the width.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Sign {
     PlusAndMinus,
     OnlyMinus,
+    SpaceAndMinus,
+    Parentheses,
+    TrailingMinus,
+    CreditDebit,
 }
 
 impl Default for Sign {
@@ -15,3 +30,29 @@ impl Default for Sign {
         Self::OnlyMinus
     }
 }
+
+/// How to render the sign of a number which renders as zero.
+///
+/// A number renders as zero when all of its rendered digits are `0`, i.e. `-0.001` with a
+/// precision of 2.
+///
+/// - `Natural`: zero takes the sign of the number it was rendered from, i.e. `-0.00` (default)
+/// - `Preserve`: as `Natural`, but floating-point negative zero also takes a negative sign
+/// - `Positive`: zero is always non-negative, i.e. `0` or `+0` according to the [`Sign`]
+/// - `Unsigned`: zero never has a leading sign, i.e. `0` even with [`Sign::PlusAndMinus`]
+/// - `PlusMinus`: zero has a leading `±`, i.e. `±0`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ZeroSign {
+    Natural,
+    Preserve,
+    Positive,
+    Unsigned,
+    PlusMinus,
+}
+
+impl Default for ZeroSign {
+    #[inline]
+    fn default() -> Self {
+        Self::Natural
+    }
+}
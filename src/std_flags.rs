@@ -0,0 +1,126 @@
+use crate::{
+    display::{display_into, OnError},
+    Align, Base, Builder, Dynamic, NumFmt, Numeric, Sign,
+};
+use core::fmt;
+
+/// Wrapper which formats a number with this crate's engine, honouring the flags of the
+/// standard library's format string at the call site.
+///
+/// The fill, alignment, width, precision, `+`, `#` and `0` flags of the [`fmt::Formatter`]
+/// override the corresponding settings of an underlying [`NumFmt`], and the formatting trait
+/// selects the base: `{:x}` is hexadecimal, `{:b}` binary, and so on. Everything else, such as
+/// separators and the decimal separator, comes from the underlying `NumFmt`.
+///
+/// ```rust
+/// # use num_runtime_fmt::{NumFmt, StdFlags};
+/// let fmt = NumFmt::builder().separator(Some(',')).build();
+/// assert_eq!(format!("{:>12.2}", fmt.std_flags(1234.5)), "    1,234.50");
+/// assert_eq!(format!("{:#x}", fmt.std_flags(0xabcdef)), "0xabc,def");
+/// assert_eq!(format!("{:+}", StdFlags::new(5)), "+5");
+/// ```
+///
+/// As with [`DisplayNum`][crate::DisplayNum], numbers which cannot be formatted are filled
+/// with `#`.
+#[derive(Clone, Copy, Debug)]
+pub struct StdFlags<'a, N> {
+    fmt: Option<&'a NumFmt>,
+    number: N,
+}
+
+impl<N> StdFlags<'_, N> {
+    /// Wrap a number to be formatted with the default settings of a [`NumFmt`], overridden by
+    /// the flags of the format string.
+    pub fn new(number: N) -> Self {
+        StdFlags { fmt: None, number }
+    }
+}
+
+impl<'a, N> StdFlags<'a, N> {
+    pub(crate) fn with_fmt(fmt: &'a NumFmt, number: N) -> Self {
+        StdFlags {
+            fmt: Some(fmt),
+            number,
+        }
+    }
+}
+
+impl<N: Numeric> StdFlags<'_, N> {
+    fn write(&self, f: &mut fmt::Formatter<'_>, base: Option<Base>) -> fmt::Result {
+        let mut builder = self.fmt.cloned().map(Builder::from).unwrap_or_default();
+        if let Some(base) = base {
+            builder = builder.base(base);
+        }
+        if let Some(align) = f.align() {
+            // an explicit alignment without the `0` flag pads with the caller's fill, outside
+            // the sign
+            if !f.sign_aware_zero_pad() {
+                builder = builder.zero(false);
+            }
+            builder = builder.fill(f.fill()).align(match align {
+                fmt::Alignment::Left => Align::Left,
+                fmt::Alignment::Center => Align::Center,
+                fmt::Alignment::Right => Align::Right,
+            });
+        }
+        if let Some(width) = f.width() {
+            builder = builder.width(width);
+        }
+        if let Some(precision) = f.precision() {
+            builder = builder.precision(Some(precision));
+        }
+        if f.sign_plus() {
+            builder = builder.sign(Sign::PlusAndMinus);
+        }
+        if f.alternate() {
+            builder = builder.hash(true);
+        }
+        if f.sign_aware_zero_pad() {
+            // as in the standard library, zero padding overrides the alignment
+            builder = builder.zero(true);
+            if matches!(
+                f.align(),
+                Some(fmt::Alignment::Left | fmt::Alignment::Center)
+            ) {
+                builder = builder.align(Align::Right);
+            }
+        }
+        display_into(
+            &builder.build(),
+            &self.number,
+            Dynamic::default(),
+            OnError::Fill,
+            f,
+        )
+    }
+}
+
+impl<N: Numeric> fmt::Display for StdFlags<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, None)
+    }
+}
+
+impl<N: Numeric> fmt::LowerHex for StdFlags<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, Some(Base::LowerHex))
+    }
+}
+
+impl<N: Numeric> fmt::UpperHex for StdFlags<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, Some(Base::UpperHex))
+    }
+}
+
+impl<N: Numeric> fmt::Binary for StdFlags<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, Some(Base::Binary))
+    }
+}
+
+impl<N: Numeric> fmt::Octal for StdFlags<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, Some(Base::Octal))
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{fmt, ops::Deref};
+
+/// Text of a setting, such as a fill pattern or a prefix.
+///
+/// A single `char` is stored inline, so that the settings of a format string never allocate.
+/// Arbitrary strings require the `alloc` feature.
+#[derive(Clone)]
+pub(crate) enum Text {
+    Char {
+        utf8: [u8; 4],
+        len: u8,
+    },
+    #[cfg(feature = "alloc")]
+    String(String),
+}
+
+impl Deref for Text {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            // only whole chars are ever encoded
+            Self::Char { utf8, len } => {
+                core::str::from_utf8(&utf8[..*len as usize]).expect("contents are valid UTF-8")
+            }
+            #[cfg(feature = "alloc")]
+            Self::String(string) => string,
+        }
+    }
+}
+
+impl Text {
+    pub(crate) const fn from_char(ch: char) -> Self {
+        let mut utf8 = [0; 4];
+        let len = ch.encode_utf8(&mut utf8).len() as u8;
+        Self::Char { utf8, len }
+    }
+}
+
+impl From<char> for Text {
+    #[inline]
+    fn from(ch: char) -> Self {
+        Self::from_char(ch)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<String> for Text {
+    fn from(string: String) -> Self {
+        Self::String(string)
+    }
+}
+
+impl PartialEq for Text {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Text {}
+
+impl fmt::Debug for Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,39 @@
+use unicode_width::UnicodeWidthChar;
+
+/// The unit in which `width` and padding are measured.
+///
+/// - `Chars`: every `char` counts as one unit of width (default)
+/// - `Columns`: every `char` counts as the number of terminal columns it occupies, according to
+///   its Unicode East Asian Width. Wide characters such as CJK ideographs and most emoji count
+///   as two columns; combining characters count as zero.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WidthUnit {
+    Chars,
+    Columns,
+}
+
+impl Default for WidthUnit {
+    #[inline]
+    fn default() -> Self {
+        Self::Chars
+    }
+}
+
+impl WidthUnit {
+    /// Width of a single `char` in this unit.
+    ///
+    /// Control characters have no defined column width; they count as zero columns.
+    #[inline]
+    pub fn char_width(self, ch: char) -> usize {
+        match self {
+            Self::Chars => 1,
+            Self::Columns => ch.width().unwrap_or_default(),
+        }
+    }
+
+    /// Width of a string in this unit.
+    #[inline]
+    pub fn str_width(self, s: &str) -> usize {
+        s.chars().map(|ch| self.char_width(ch)).sum()
+    }
+}
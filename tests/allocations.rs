@@ -0,0 +1,125 @@
+//! Streaming into a writer does not allocate, even with the `alloc` feature.
+#![cfg(feature = "std")]
+
+use num_runtime_fmt::{Currency, Dynamic, NumFmt, Numeric};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Write,
+};
+
+/// Allocator counting the allocations of each thread, so that tests can run in parallel.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Count of the allocations made by `f` on this thread.
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+const SPECS: &[&str] = &[
+    "", "+", "*^12.2,", "010.3_", "v8.2", "#x_4", "#b", ".3e", "E", ".1%", "+08.2‰", "bp_",
+    "(10.2,", "t", "c8", " 5d", "z.0", "±6.1",
+];
+
+const FLOATS: &[f64] = &[
+    0.0,
+    -0.0,
+    1.5,
+    -1234.5678,
+    0.000123,
+    6.02214076e23,
+    1e300,
+    f64::MIN_POSITIVE,
+    f64::NAN,
+];
+
+fn configurations() -> Vec<NumFmt> {
+    let mut fmts: Vec<NumFmt> = SPECS.iter().map(|spec| spec.parse().unwrap()).collect();
+    fmts.push(
+        NumFmt::builder()
+            .currency(Currency::from_code("EUR"))
+            .separator(Some('.'))
+            .decimal_separator(',')
+            .width(14)
+            .build(),
+    );
+    fmts.push(
+        NumFmt::builder()
+            .fill_pattern("-=")
+            .prefix("≈ ")
+            .suffix(" units")
+            .width(24)
+            .build(),
+    );
+    fmts
+}
+
+/// Assert that writing `number` with `fmt` does not allocate, whether or not it succeeds.
+fn assert_no_allocations<N: Numeric + Copy + std::fmt::Debug>(fmt: &NumFmt, number: N) {
+    let mut out = String::with_capacity(1024);
+    let count = allocations(|| drop(fmt.write_to(&mut out, number)));
+    assert_eq!(count, 0, "write_to {:?} with {:?}", number, fmt);
+
+    out.clear();
+    let dynamic = Dynamic::new(20, 2, None);
+    let count = allocations(|| drop(fmt.write_to_with(&mut out, number, dynamic)));
+    assert_eq!(count, 0, "write_to_with {:?} with {:?}", number, fmt);
+
+    out.clear();
+    let count = allocations(|| write!(out, "{}", fmt.display(number)).unwrap());
+    assert_eq!(count, 0, "display {:?} with {:?}", number, fmt);
+
+    if let Ok(plan) = fmt.compile::<N>(Dynamic::default()) {
+        out.clear();
+        let count = allocations(|| drop(plan.write_to(&mut out, number)));
+        assert_eq!(count, 0, "plan {:?} with {:?}", number, fmt);
+    }
+}
+
+#[test]
+fn floats() {
+    for fmt in configurations() {
+        for &number in FLOATS {
+            assert_no_allocations(&fmt, number);
+            assert_no_allocations(&fmt, number as f32);
+        }
+    }
+}
+
+#[test]
+fn integers() {
+    for fmt in configurations() {
+        for number in [0, 7, -42, 1_234_567, i64::MIN, i64::MAX] {
+            assert_no_allocations(&fmt, number);
+            assert_no_allocations(&fmt, number as u64);
+            assert_no_allocations(&fmt, number as i8);
+        }
+        assert_no_allocations(&fmt, u128::MAX);
+        assert_no_allocations(&fmt, i128::MIN);
+    }
+}
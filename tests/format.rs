@@ -1,3 +1,7 @@
+#![cfg(feature = "alloc")]
+// 3.14159 is an arbitrary fraction here, not an approximation of pi
+#![allow(clippy::approx_constant)]
+
 use num_runtime_fmt::{Dynamic, Error, NumFmt};
 
 macro_rules! test_mod {
@@ -81,6 +85,22 @@ test_mod! { align:
     bigger_decimal_float("v5", 11.1, "   11.1");
 }
 
+test_mod! { after_sign:
+    pos_int("=5", 1, "    1");
+    neg_int("=5", -1, "-   1");
+    plus_int("=+5", 1, "+   1");
+    fill_neg_int("*=5", -1, "-***1");
+    fill_neg_float("*=6", -1.5, "-**1.5");
+    hash("_=#6x", 255, "0x__ff");
+    sign_and_hash("_=+#7x", 255, "+0x__ff");
+    paren(".=(6", -12, "(..12)");
+    paren_pos(".=(6", 12, "...12 ");
+    zero("=05", -1, "-0001");
+    zero_separated("=07,", 1, "000,001");
+    narrow("*=1", -123, "-123");
+    dyn_width("*=", Dynamic::width(4), -1, "-**1");
+}
+
 test_mod! { sign:
     omit_pos_int("",   1, "1");
     omit_neg_int("",  -1, "-1");
@@ -95,6 +115,62 @@ test_mod! { sign:
     plus_neg_float("+", -1.1, "-1.1");
     mins_pos_float("-",  1.1, "1.1");
     mins_neg_float("-", -1.1, "-1.1");
+
+    // the digit before the decimal survives the sign
+    plus_zero_int("+", 0, "+0");
+    plus_zero_float("+", 0.0, "+0");
+    plus_fraction("+", 0.5, "+0.5");
+    plus_neg_fraction("+", -0.5, "-0.5");
+    plus_zero_width("+3", 0, " +0");
+
+    paren_pos_int("(", 1, "1 ");
+    paren_neg_int("(", -1, "(1)");
+    paren_pos_float("(", 1.1, "1.1 ");
+    paren_neg_float("(", -1.1, "(1.1)");
+    trail_pos_int("t", 1, "1 ");
+    trail_neg_int("t", -1, "1-");
+    credit_debit_pos_float("c", 1.1, "1.1   ");
+    credit_debit_neg_float("c", -1.1, "1.1 CR");
+    credit_debit_zero("c", 0, "0   ");
+
+    space_pos_int(" d", 1, " 1");
+    space_neg_int(" d", -1, "-1");
+    space_pos_float(" .1", 1.1, " 1.1");
+    space_neg_float(" .1", -1.1, "-1.1");
+    space_width(" 4d", 1, "   1");
+    space_zero(" 04d", 1, " 001");
+    space_left("< 4d", 1, " 1  ");
+}
+
+test_mod! { zero_sign:
+    natural_neg(".1", -0.01, "-0.0");
+    natural_neg_zero(".1", -0.0, "0.0");
+    natural_plus("+.1", 0.0, "+0.0");
+    positive("z.1", -0.01, "0.0");
+    positive_plus("+z.1", -0.01, "+0.0");
+    positive_space(" z.1", -0.01, " 0.0");
+    positive_nonzero("z.1", -0.1, "-0.1");
+    plus_minus("±.1", -0.01, "±0.0");
+    plus_minus_int("±", 0, "±0");
+    plus_minus_nonzero("±", -1, "-1");
+    plus_minus_width("±4", 0, "  ±0");
+    plus_minus_zero("±04", 0, "±000");
+    paren_positive("(z.1", -0.01, "0.0 ");
+}
+
+test_mod! { accounting:
+    paren_width_pos("(10.2,", 1234, " 1,234.00 ");
+    paren_width_neg("(10.2,", -1234, "(1,234.00)");
+    paren_left("<(6", -12, "(12)  ");
+    paren_zero("(06", -12, "(0012)");
+    paren_zero_pos("(06", 12, "00012 ");
+    paren_decimal("v(4.2", -12, " (12.00)");
+    paren_decimal_pos("v(4.2", 12, "  12.00 ");
+    trail_width("t6", -12, "   12-");
+    trail_center("^t6", -12, "  12- ");
+    credit_debit_width("c8", 12, "   12   ");
+    credit_debit_width_neg("c8", -12, "   12 CR");
+    credit_debit_zero("c08", -12, "00012 CR");
 }
 
 test_mod! { hash:
@@ -148,6 +224,8 @@ test_mod! { width:
     default_align_neg_int("5", -1, "   -1");
     default_align_pos_float("5", 1.1, "  1.1");
     default_align_neg_float("5", -1.1, " -1.1");
+    left_neg_int("<5", -1, "-1   ");
+    center_neg_int("^5", -1, "  -1 ");
 
     dyn_default_align_pos_int("", Dynamic::width(5), 1, "    1");
     dyn_default_align_neg_int("", Dynamic::width(5), -1, "   -1");
@@ -158,6 +236,8 @@ test_mod! { width:
 test_mod! { precision:
     truncate(".2", 3.14159, "3.14");
     extend(".7", 3.14159, "3.1415900");
+    zero_precision(".0", 3.14159, "3");
+    zero_precision_int(".0", 3, "3");
 
     dyn_truncate("", Dynamic::precision(2), 3.14159, "3.14");
     dyn_extend("", Dynamic::precision(7), 3.14159, "3.1415900");
@@ -175,6 +255,8 @@ test_mod! { base:
     octal("04o", 0o644, "0644");
     lower_hex("x 4", 0xcafebabe_u32, "cafe babe");
     upper_hex("#X_4", 0xDEADBEEF_u32, "0xDEAD_BEEF");
+    hash_width("#6x", 1, "   0x1");
+    hash_width_left("<#6x", 1, "0x1   ");
 
     fmt_fail binary_float("09b_4", 0.0, Error::NotImplemented(_, _));
     fmt_fail octal_float("04o", 0.0, Error::NotImplemented(_, _));
@@ -212,6 +294,7 @@ test_mod! { spacing:
     dyn_comma_float(",", Dynamic::spacing(4), 123456789.0, "1,2345,6789");
     dyn_space_float(" ", Dynamic::spacing(5), 123456789.0, "1234 56789");
     dyn_under_float("_", Dynamic::spacing(6), 123456789.0, "123_456789");
+    dyn_zero(",", Dynamic::spacing(0), 123456789, "123456789");
 
     // TODO: do we _want_ post-decimal commas? Those feel weird to me somehow.
     only_pre_decimal(".9,7", 123456789.87654321, "12,3456789.876543210");
@@ -240,6 +323,14 @@ mod only_spacing {
         let have = fmt.fmt(0b0110_1110).unwrap();
         assert_eq!(have, "0110,1110");
     }
+
+    #[test]
+    fn zero() {
+        let fmt = NumFmt::builder().spacing(0).separator(Some('_')).build();
+        assert_eq!(fmt.separator(), None);
+        assert_eq!(fmt.spacing(), None);
+        assert_eq!(fmt.fmt(123456789).unwrap(), "123456789");
+    }
 }
 
 mod misc {
@@ -275,3 +366,1927 @@ mod misc {
         assert_eq!(formatted, "0x0f");
     }
 }
+
+test_mod! { native_digits:
+    i8_min("", i8::MIN, "-128");
+    i128_min("", i128::MIN, "-170141183460469231731687303715884105728");
+    u128_max(",", u128::MAX, "340,282,366,920,938,463,463,374,607,431,768,211,455");
+    u128_chunk_zeros("", 10_u128.pow(30) + 7, "1000000000000000000000000000007");
+    negative_hex("x", -1_i32, "ffffffff");
+    negative_binary("b", -2_i8, "11111110");
+    negative_octal("o", i16::MIN, "100000");
+    float_large("", 1e21, "1000000000000000000000");
+    float_small("", 1.5e-7, "0.00000015");
+    float_shortest("", 0.1 + 0.2, "0.30000000000000004");
+    float_f32("", 16_777_217_f32, "16777216");
+}
+
+test_mod! { ratio:
+    percent_float("%", 0.5, "50%");
+    percent_precision(".1%", 0.1234, "12.3%");
+    percent_int("%", 3, "300%");
+    percent_neg("%", -0.25, "-25%");
+    percent_small(".2%", 0.000_1, "0.01%");
+    percent_width("6%", 0.5, "   50%");
+    percent_zero("06%", 0.5, "00050%");
+    percent_left("<6%", 0.5, "50%   ");
+    percent_decimal("v3.1%", 0.5, " 50.0%");
+    per_mille("‰", 0.0125, "12.5‰");
+    basis_point("bp", 0.0125, "125bp");
+    basis_point_separated("bp,", 12.5, "125,000bp");
+}
+
+test_mod! { non_finite:
+    nan("", f64::NAN, "NaN");
+    inf("", f64::INFINITY, "inf");
+    neg_inf("", f32::NEG_INFINITY, "-inf");
+    nan_precision(".2,", f64::NAN, "NaN");
+    inf_separated(",", f64::INFINITY, "inf");
+    inf_plus("+", f64::INFINITY, "+inf");
+    neg_inf_paren("(", f32::NEG_INFINITY, "(inf)");
+    inf_width("6", f64::INFINITY, "   inf");
+    neg_inf_zero("06", f32::NEG_INFINITY, "-00inf");
+    nan_scientific("e", f64::NAN, "NaN");
+    inf_scientific(".3E", f64::INFINITY, "inf");
+    neg_inf_scientific("e", f32::NEG_INFINITY, "-inf");
+    nan_percent("%", f64::NAN, "NaN%");
+    inf_percent("%", f64::INFINITY, "inf%");
+    neg_inf_basis_point("bp", f32::NEG_INFINITY, "-infbp");
+    inf_percent_width(">6%", f64::INFINITY, "  inf%");
+}
+
+mod scale {
+    use super::*;
+    use num_runtime_fmt::{Base, Ratio};
+
+    #[test]
+    fn thousands() {
+        let fmt = NumFmt::builder().scale(-3).precision(Some(1)).build();
+        assert_eq!(fmt.fmt(1_234_567).unwrap(), "1234.5");
+    }
+
+    #[test]
+    fn thousands_small() {
+        let fmt = NumFmt::builder().scale(-3).build();
+        assert_eq!(fmt.fmt(12).unwrap(), "0.012");
+    }
+
+    #[test]
+    fn upscale() {
+        let fmt = NumFmt::builder().scale(2).build();
+        assert_eq!(fmt.fmt(1.5).unwrap(), "150");
+    }
+
+    #[test]
+    fn combines_with_ratio() {
+        let fmt = NumFmt::builder()
+            .scale(-2)
+            .ratio(Some(Ratio::Percent))
+            .build();
+        assert_eq!(fmt.fmt(42).unwrap(), "42%");
+    }
+
+    #[test]
+    fn no_float_error() {
+        let fmt = NumFmt::builder().ratio(Some(Ratio::Percent)).build();
+        // `0.07 * 100.0` is `7.000000000000001`
+        assert_eq!(fmt.fmt(0.07).unwrap(), "7%");
+    }
+
+    #[test]
+    fn non_decimal() {
+        let fmt = NumFmt::builder().scale(1).base(Base::LowerHex).build();
+        assert_eq!(fmt.fmt(1).unwrap_err(), Error::IncompatibleScale);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let fmt = NumFmt::builder()
+            .scale(i32::MAX)
+            .ratio(Some(Ratio::Percent))
+            .build();
+        assert_eq!(fmt.fmt(1.5).unwrap_err(), Error::ScaleOutOfRange);
+
+        let fmt = NumFmt::builder().scale(401).build();
+        assert_eq!(fmt.fmt(1).unwrap_err(), Error::ScaleOutOfRange);
+        let fmt = NumFmt::builder().scale(i32::MIN).build();
+        assert_eq!(fmt.fmt(1).unwrap_err(), Error::ScaleOutOfRange);
+
+        let fmt = NumFmt::builder().scale(-400).build();
+        assert!(fmt.fmt(f64::MAX).unwrap().ends_with("00017976931348623157"));
+        let fmt = NumFmt::builder()
+            .scale(398)
+            .ratio(Some(Ratio::Percent))
+            .build();
+        assert!(fmt.fmt(5e-324).unwrap().starts_with("5000"));
+    }
+}
+
+mod zero_sign_builder {
+    use super::*;
+    use num_runtime_fmt::{Sign, ZeroSign};
+
+    #[test]
+    fn preserve() {
+        let fmt = NumFmt::builder().zero_sign(ZeroSign::Preserve).build();
+        assert_eq!(fmt.fmt(-0.0).unwrap(), "-0");
+        assert_eq!(fmt.fmt(0.0).unwrap(), "0");
+        assert_eq!(fmt.fmt(-1.0).unwrap(), "-1");
+    }
+
+    #[test]
+    fn unsigned() {
+        let fmt = NumFmt::builder()
+            .sign(Sign::PlusAndMinus)
+            .zero_sign(ZeroSign::Unsigned)
+            .precision(Some(1))
+            .build();
+        assert_eq!(fmt.fmt(-0.01).unwrap(), "0.0");
+        assert_eq!(fmt.fmt(0).unwrap(), "0.0");
+        assert_eq!(fmt.fmt(1).unwrap(), "+1.0");
+    }
+}
+
+mod currency {
+    use super::*;
+    use num_runtime_fmt::{Align, Builder, Currency, CurrencyDisplay, Sign};
+
+    fn fmt_for(code: &str) -> NumFmt {
+        NumFmt::builder()
+            .currency(Currency::from_code(code))
+            .separator(Some(','))
+            .build()
+    }
+
+    #[test]
+    fn minor_units() {
+        assert_eq!(fmt_for("JPY").fmt(1234.5).unwrap(), "¥1,234");
+        assert_eq!(fmt_for("USD").fmt(1234).unwrap(), "$1,234.00");
+        assert_eq!(fmt_for("KWD").fmt(1234.5).unwrap(), "KWD 1,234.500");
+    }
+
+    #[test]
+    fn explicit_precision_wins() {
+        let fmt = NumFmt::builder()
+            .currency(Currency::from_code("USD"))
+            .precision(Some(4))
+            .build();
+        assert_eq!(fmt.fmt(1.5).unwrap(), "$1.5000");
+        assert_eq!(fmt.fmt_with(1.5, Dynamic::precision(1)).unwrap(), "$1.5");
+    }
+
+    #[test]
+    fn negative_prefix() {
+        assert_eq!(fmt_for("USD").fmt(-5).unwrap(), "-$5.00");
+        assert_eq!(fmt_for("CHF").fmt(-5).unwrap(), "CHF -5.00");
+    }
+
+    #[test]
+    fn suffix() {
+        assert_eq!(fmt_for("EUR").fmt(-5).unwrap(), "-5.00 €");
+        assert_eq!(fmt_for("SEK").fmt(1000).unwrap(), "1,000.00 kr");
+    }
+
+    #[test]
+    fn code() {
+        let fmt = NumFmt::builder()
+            .currency(Currency::from_code("EUR"))
+            .currency_display(CurrencyDisplay::Code)
+            .build();
+        assert_eq!(fmt.fmt(5).unwrap(), "5.00 EUR");
+    }
+
+    #[test]
+    fn width() {
+        let fmt = NumFmt::builder()
+            .currency(Currency::from_code("USD"))
+            .width(8)
+            .build();
+        assert_eq!(fmt.fmt(-5).unwrap(), "  -$5.00");
+
+        let fmt = NumFmt::builder()
+            .currency(Currency::from_code("EUR"))
+            .width(9)
+            .align(Align::Left)
+            .build();
+        assert_eq!(fmt.fmt(5).unwrap(), "5.00 €   ");
+    }
+
+    #[test]
+    fn zero() {
+        let fmt = NumFmt::builder()
+            .currency(Currency::from_code("USD"))
+            .zero(true)
+            .width(8)
+            .build();
+        assert_eq!(fmt.fmt(5).unwrap(), "$0005.00");
+        assert_eq!(fmt.fmt(-5).unwrap(), "-$005.00");
+    }
+
+    #[test]
+    fn parentheses() {
+        let fmt = NumFmt::builder()
+            .currency(Currency::from_code("USD"))
+            .sign(Sign::Parentheses)
+            .width(10)
+            .build();
+        assert_eq!(fmt.fmt(-5).unwrap(), "   ($5.00)");
+        assert_eq!(fmt.fmt(5).unwrap(), "    $5.00 ");
+
+        let fmt = Builder::from(fmt)
+            .currency(Currency::from_code("EUR"))
+            .build();
+        assert_eq!(fmt.fmt(-5).unwrap(), "  (5.00 €)");
+    }
+
+    #[test]
+    fn decimal_align() {
+        let fmt = NumFmt::builder()
+            .currency(Currency::from_code("EUR"))
+            .align(Align::Decimal)
+            .width(4)
+            .build();
+        assert_eq!(fmt.fmt(5).unwrap(), "   5.00 €");
+        assert_eq!(fmt.fmt(-50).unwrap(), " -50.00 €");
+    }
+}
+
+mod sign_chars {
+    use super::*;
+    use num_runtime_fmt::{Align, Sign};
+
+    #[test]
+    fn unicode_minus() {
+        let fmt = NumFmt::builder().minus_sign('\u{2212}').build();
+        assert_eq!(fmt.fmt(-1.5).unwrap(), "\u{2212}1.5");
+        assert_eq!(fmt.fmt(1.5).unwrap(), "1.5");
+    }
+
+    #[test]
+    fn full_width_minus_pads_by_char() {
+        let fmt = NumFmt::builder()
+            .minus_sign('－')
+            .width(4)
+            .zero(true)
+            .build();
+        assert_eq!(fmt.fmt(-1).unwrap(), "－001");
+    }
+
+    #[test]
+    fn custom_plus() {
+        let fmt = NumFmt::builder()
+            .sign(Sign::PlusAndMinus)
+            .plus_sign('▲')
+            .minus_sign('▼')
+            .width(4)
+            .align(Align::Left)
+            .build();
+        assert_eq!(fmt.fmt(3).unwrap(), "▲3  ");
+        assert_eq!(fmt.fmt(-3).unwrap(), "▼3  ");
+    }
+
+    #[test]
+    fn trailing_minus() {
+        let fmt = NumFmt::builder()
+            .sign(Sign::TrailingMinus)
+            .minus_sign('\u{2212}')
+            .build();
+        assert_eq!(fmt.fmt(-3).unwrap(), "3\u{2212}");
+    }
+
+    #[test]
+    fn plus_unused_by_space_sign() {
+        let fmt = NumFmt::builder()
+            .sign(Sign::SpaceAndMinus)
+            .plus_sign('▲')
+            .build();
+        assert_eq!(fmt.fmt(3).unwrap(), " 3");
+    }
+}
+
+mod base_marker {
+    use super::*;
+    use num_runtime_fmt::{Base, BaseMarker, Builder};
+
+    fn fmt_for(base: Base, marker: BaseMarker) -> NumFmt {
+        NumFmt::builder()
+            .base(base)
+            .hash(true)
+            .base_marker(marker)
+            .build()
+    }
+
+    #[test]
+    fn c() {
+        assert_eq!(
+            fmt_for(Base::UpperHex, BaseMarker::C).fmt(31).unwrap(),
+            "0X1F"
+        );
+        assert_eq!(
+            fmt_for(Base::LowerHex, BaseMarker::C).fmt(31).unwrap(),
+            "0x1f"
+        );
+        assert_eq!(fmt_for(Base::Octal, BaseMarker::C).fmt(8).unwrap(), "010");
+        assert_eq!(fmt_for(Base::Decimal, BaseMarker::C).fmt(8).unwrap(), "8");
+    }
+
+    #[test]
+    fn intel() {
+        assert_eq!(
+            fmt_for(Base::UpperHex, BaseMarker::Intel).fmt(31).unwrap(),
+            "1Fh"
+        );
+        assert_eq!(
+            fmt_for(Base::Binary, BaseMarker::Intel).fmt(5).unwrap(),
+            "101b"
+        );
+    }
+
+    #[test]
+    fn motorola() {
+        assert_eq!(
+            fmt_for(Base::UpperHex, BaseMarker::Motorola)
+                .fmt(31)
+                .unwrap(),
+            "$1F"
+        );
+        assert_eq!(
+            fmt_for(Base::Binary, BaseMarker::Motorola).fmt(5).unwrap(),
+            "%101"
+        );
+    }
+
+    #[test]
+    fn visual_basic() {
+        assert_eq!(
+            fmt_for(Base::UpperHex, BaseMarker::VisualBasic)
+                .fmt(31)
+                .unwrap(),
+            "&H1F"
+        );
+    }
+
+    #[test]
+    fn custom() {
+        let marker = BaseMarker::Custom {
+            prefix: "#".into(),
+            suffix: "".into(),
+        };
+        assert_eq!(fmt_for(Base::LowerHex, marker).fmt(0xabc).unwrap(), "#abc");
+    }
+
+    #[test]
+    fn requires_hash() {
+        let fmt = NumFmt::builder()
+            .base(Base::UpperHex)
+            .base_marker(BaseMarker::Intel)
+            .build();
+        assert_eq!(fmt.fmt(31).unwrap(), "1F");
+    }
+
+    #[test]
+    fn suffix_counts_toward_width() {
+        let fmt = Builder::from(fmt_for(Base::UpperHex, BaseMarker::Intel))
+            .width(6)
+            .zero(true)
+            .build();
+        assert_eq!(fmt.fmt(31).unwrap(), "0001Fh");
+    }
+
+    #[test]
+    fn zero_pads_after_prefix() {
+        let fmt = Builder::from(fmt_for(Base::UpperHex, BaseMarker::Motorola))
+            .width(5)
+            .zero(true)
+            .build();
+        assert_eq!(fmt.fmt(31).unwrap(), "$001F");
+    }
+}
+
+mod affix {
+    use super::*;
+    use num_runtime_fmt::{Align, Base, Sign};
+
+    #[test]
+    fn unit() {
+        let fmt = NumFmt::builder().suffix("ms").width(6).build();
+        assert_eq!(fmt.fmt(15).unwrap(), "  15ms");
+        assert_eq!(fmt.fmt(-15).unwrap(), " -15ms");
+    }
+
+    #[test]
+    fn unit_left() {
+        let fmt = NumFmt::builder()
+            .suffix("°C")
+            .width(6)
+            .align(Align::Left)
+            .build();
+        assert_eq!(fmt.fmt(-4).unwrap(), "-4°C  ");
+    }
+
+    #[test]
+    fn unit_decimal() {
+        let fmt = NumFmt::builder()
+            .suffix(" ms")
+            .width(3)
+            .align(Align::Decimal)
+            .build();
+        assert_eq!(fmt.fmt(1.5).unwrap(), "  1.5 ms");
+    }
+
+    #[test]
+    fn prefix_precedes_sign() {
+        let fmt = NumFmt::builder()
+            .prefix("Δ")
+            .sign(Sign::PlusAndMinus)
+            .width(5)
+            .build();
+        assert_eq!(fmt.fmt(12).unwrap(), " Δ+12");
+    }
+
+    #[test]
+    fn zero_pads_inside_affixes() {
+        let fmt = NumFmt::builder()
+            .prefix("[")
+            .suffix("]")
+            .zero(true)
+            .width(6)
+            .build();
+        assert_eq!(fmt.fmt(-1).unwrap(), "[-001]");
+    }
+
+    #[test]
+    fn with_base_marker() {
+        let fmt = NumFmt::builder()
+            .prefix("addr=")
+            .base(Base::LowerHex)
+            .hash(true)
+            .zero(true)
+            .width(11)
+            .build();
+        assert_eq!(fmt.fmt(0xbeef).unwrap(), "addr=0xbeef");
+        assert_eq!(fmt.fmt(0xef).unwrap(), "addr=0x00ef");
+    }
+}
+
+mod fill_pattern {
+    use super::*;
+    use num_runtime_fmt::Align;
+
+    #[test]
+    fn repeats() {
+        let fmt = NumFmt::builder().fill_pattern(".-").width(6).build();
+        assert_eq!(fmt.fmt(1).unwrap(), ".-.-.1");
+        assert_eq!(fmt.fmt(12).unwrap(), ".-.-12");
+    }
+
+    #[test]
+    fn anchored_to_output() {
+        let fmt = NumFmt::builder()
+            .fill_pattern("abc")
+            .align(Align::Left)
+            .width(6)
+            .build();
+        assert_eq!(fmt.fmt(1).unwrap(), "1bcabc");
+        assert_eq!(fmt.fmt(12).unwrap(), "12cabc");
+    }
+
+    #[test]
+    fn leaders() {
+        let fmt = NumFmt::builder()
+            .prefix("Intro ")
+            .suffix(" p")
+            .align(Align::AfterSign)
+            .fill_pattern(". ")
+            .width(14)
+            .build();
+        assert_eq!(fmt.fmt(3).unwrap(), "Intro . . .3 p");
+        assert_eq!(fmt.fmt(45).unwrap(), "Intro . . 45 p");
+    }
+
+    #[test]
+    fn center_asymmetric() {
+        let fmt = NumFmt::builder()
+            .fill_pattern("=>")
+            .right_fill_pattern("<=")
+            .align(Align::Center)
+            .width(9)
+            .build();
+        assert_eq!(fmt.fmt(-1).unwrap(), "=>=>-1<=<");
+    }
+
+    #[test]
+    fn right_fill_defaults_to_fill() {
+        let fmt = NumFmt::builder()
+            .fill('*')
+            .align(Align::Center)
+            .width(5)
+            .build();
+        assert_eq!(fmt.right_fill_pattern(), "*");
+        assert_eq!(fmt.fmt(1).unwrap(), "**1**");
+    }
+
+    #[test]
+    fn wide_chars() {
+        let fmt = NumFmt::builder().fill_pattern("🖤💛").width(4).build();
+        assert_eq!(fmt.fmt(1).unwrap(), "🖤💛🖤1");
+    }
+
+    #[test]
+    fn fill_char_is_first_of_pattern() {
+        let fmt = NumFmt::builder().fill_pattern("-=").build();
+        assert_eq!(fmt.fill(), '-');
+        assert_eq!(fmt.fill_pattern(), "-=");
+    }
+
+    #[test]
+    fn zero_pattern_is_not_zero_handler() {
+        let fmt = NumFmt::builder().fill_pattern("00").width(4).build();
+        assert!(!fmt.zero());
+    }
+}
+
+mod width_unit {
+    use super::*;
+    use num_runtime_fmt::{Align, Builder, WidthUnit};
+
+    fn columns() -> Builder {
+        NumFmt::builder().width_unit(WidthUnit::Columns)
+    }
+
+    #[test]
+    fn chars_by_default() {
+        let fmt = NumFmt::builder().fill('🖤').width(3).build();
+        assert_eq!(fmt.width_unit(), WidthUnit::Chars);
+        assert_eq!(fmt.fmt(1).unwrap(), "🖤🖤1");
+    }
+
+    #[test]
+    fn wide_fill() {
+        let fmt = columns().fill('🖤').width(5).build();
+        assert_eq!(fmt.fmt(1).unwrap(), "🖤🖤1");
+    }
+
+    #[test]
+    fn wide_fill_remainder_is_space() {
+        let fmt = columns().fill('🖤').width(4).build();
+        assert_eq!(fmt.fmt(1).unwrap(), "🖤 1");
+    }
+
+    #[test]
+    fn wide_suffix() {
+        let fmt = columns().suffix("円").width(6).build();
+        assert_eq!(fmt.fmt(500).unwrap(), " 500円");
+    }
+
+    #[test]
+    fn combining_suffix() {
+        // "°" followed by U+0301 COMBINING ACUTE ACCENT occupies a single column
+        let fmt = columns().suffix("°\u{301}").width(4).build();
+        assert_eq!(fmt.fmt(12).unwrap(), " 12°\u{301}");
+    }
+
+    #[test]
+    fn wide_separator() {
+        let fmt = columns().separator(Some('，')).width(10).build();
+        assert_eq!(fmt.fmt(1234567).unwrap(), "1，234，567");
+        assert_eq!(fmt.fmt(1234).unwrap(), "    1，234");
+    }
+
+    #[test]
+    fn wide_separator_zero() {
+        let fmt = columns().separator(Some('，')).zero(true).width(8).build();
+        assert_eq!(fmt.fmt(1).unwrap(), "000，001");
+    }
+
+    #[test]
+    fn wide_sign_zero() {
+        let fmt = columns().minus_sign('－').zero(true).width(5).build();
+        assert_eq!(fmt.fmt(-1).unwrap(), "－001");
+    }
+
+    #[test]
+    fn wide_fill_center() {
+        let fmt = columns().fill('＊').align(Align::Center).width(7).build();
+        assert_eq!(fmt.fmt(1).unwrap(), "＊ 1＊ ");
+    }
+
+    #[test]
+    fn zero_width_pattern_falls_back_to_space() {
+        let fmt = columns().fill_pattern("\u{301}").width(3).build();
+        assert_eq!(fmt.fmt(1).unwrap(), "  1");
+    }
+}
+
+mod overflow {
+    use num_runtime_fmt::{Base, Builder, Error, NumFmt, Overflow, WidthUnit};
+
+    fn limited(max_width: usize, overflow: Overflow) -> Builder {
+        NumFmt::builder()
+            .max_width(Some(max_width))
+            .overflow(overflow)
+    }
+
+    #[test]
+    fn fits() {
+        for overflow in [
+            Overflow::Extend,
+            Overflow::Error,
+            Overflow::Fill,
+            Overflow::Truncate,
+            Overflow::Scientific,
+        ] {
+            let fmt = limited(5, overflow).build();
+            assert_eq!(fmt.fmt(12345).unwrap(), "12345");
+        }
+    }
+
+    #[test]
+    fn extend() {
+        let fmt = limited(3, Overflow::Extend).build();
+        assert_eq!(fmt.fmt(12345).unwrap(), "12345");
+    }
+
+    #[test]
+    fn error() {
+        let fmt = limited(3, Overflow::Error).build();
+        assert_eq!(fmt.fmt(-12345).unwrap_err(), Error::Overflow(6, 3));
+    }
+
+    #[test]
+    fn fill() {
+        let fmt = limited(4, Overflow::Fill).build();
+        assert_eq!(fmt.fmt(12345).unwrap(), "####");
+        assert_eq!(fmt.fmt(1.5).unwrap(), "1.5");
+    }
+
+    #[test]
+    fn width_is_capped() {
+        let fmt = limited(4, Overflow::Error).width(8).build();
+        assert_eq!(fmt.fmt(12).unwrap(), "  12");
+    }
+
+    #[test]
+    fn truncate() {
+        let fmt = limited(6, Overflow::Truncate).separator(Some(',')).build();
+        assert_eq!(fmt.fmt(123456789).unwrap(), "123,4…");
+    }
+
+    #[test]
+    fn truncate_wide() {
+        let fmt = limited(6, Overflow::Truncate)
+            .width_unit(WidthUnit::Columns)
+            .prefix("長さ")
+            .build();
+        // the ellipsis counts as a single column
+        assert_eq!(fmt.fmt(12345).unwrap(), "長さ1…");
+    }
+
+    #[test]
+    fn scientific() {
+        let fmt = limited(7, Overflow::Scientific).build();
+        assert_eq!(fmt.fmt(123456789).unwrap(), "1.234e8");
+        assert_eq!(fmt.fmt(-123456789).unwrap(), "-1.23e8");
+        assert_eq!(fmt.fmt(0.000012345).unwrap(), "1.23e-5");
+    }
+
+    #[test]
+    fn scientific_keeps_affixes() {
+        let fmt = limited(10, Overflow::Scientific)
+            .precision(Some(2))
+            .suffix(" m")
+            .width(10)
+            .build();
+        assert_eq!(fmt.fmt(1.5).unwrap(), "    1.50 m");
+        assert_eq!(fmt.fmt(1234567.5).unwrap(), "1.2345e6 m");
+    }
+
+    #[test]
+    fn scientific_falls_back_to_fill() {
+        let fmt = limited(3, Overflow::Scientific).build();
+        assert_eq!(fmt.fmt(123456789).unwrap(), "1e8");
+        assert_eq!(fmt.fmt(-123456789).unwrap(), "###");
+
+        let fmt = limited(3, Overflow::Scientific)
+            .base(Base::LowerHex)
+            .build();
+        assert_eq!(fmt.fmt(0xabcd).unwrap(), "###");
+    }
+}
+
+mod integer_digits {
+    use num_runtime_fmt::{Align, Base, NumFmt};
+
+    #[test]
+    fn min_pads_with_zeros() {
+        let fmt = NumFmt::builder().min_integer_digits(Some(5)).build();
+        assert_eq!(fmt.fmt(42).unwrap(), "00042");
+        assert_eq!(fmt.fmt(-42).unwrap(), "-00042");
+        assert_eq!(fmt.fmt(1234567).unwrap(), "1234567");
+        assert_eq!(fmt.fmt(0.5).unwrap(), "00000.5");
+    }
+
+    #[test]
+    fn min_with_fill() {
+        let fmt = NumFmt::builder()
+            .min_integer_digits(Some(5))
+            .fill('*')
+            .align(Align::Left)
+            .width(10)
+            .build();
+        assert_eq!(fmt.fmt(-42).unwrap(), "-00042****");
+    }
+
+    #[test]
+    fn min_with_separator() {
+        let fmt = NumFmt::builder()
+            .min_integer_digits(Some(5))
+            .separator(Some(','))
+            .build();
+        assert_eq!(fmt.fmt(42).unwrap(), "00,042");
+    }
+
+    #[test]
+    fn min_with_zero_handler() {
+        let fmt = NumFmt::builder()
+            .min_integer_digits(Some(3))
+            .zero(true)
+            .width(5)
+            .build();
+        assert_eq!(fmt.fmt(-4).unwrap(), "-0004");
+        // the sign does not displace the required digits
+        let fmt = NumFmt::builder()
+            .min_integer_digits(Some(3))
+            .zero(true)
+            .width(3)
+            .build();
+        assert_eq!(fmt.fmt(-4).unwrap(), "-004");
+    }
+
+    #[test]
+    fn min_hex() {
+        let fmt = NumFmt::builder()
+            .min_integer_digits(Some(4))
+            .base(Base::UpperHex)
+            .hash(true)
+            .build();
+        assert_eq!(fmt.fmt(0xab).unwrap(), "0x00AB");
+    }
+
+    #[test]
+    fn max_keeps_low_order_digits() {
+        let fmt = NumFmt::builder().max_integer_digits(Some(4)).build();
+        assert_eq!(fmt.fmt(1234567).unwrap(), "4567");
+        assert_eq!(fmt.fmt(-98765.25).unwrap(), "-8765.25");
+        assert_eq!(fmt.fmt(12).unwrap(), "12");
+    }
+
+    #[test]
+    fn min_and_max() {
+        let fmt = NumFmt::builder()
+            .min_integer_digits(Some(6))
+            .max_integer_digits(Some(6))
+            .build();
+        assert_eq!(fmt.fmt(1234).unwrap(), "001234");
+        assert_eq!(fmt.fmt(100001234).unwrap(), "001234");
+    }
+}
+
+mod fraction_digits {
+    use num_runtime_fmt::{Align, Dynamic, NumFmt};
+
+    #[test]
+    fn trim_trailing_zeros() {
+        let fmt = NumFmt::builder()
+            .precision(Some(3))
+            .min_fraction_digits(Some(0))
+            .build();
+        assert_eq!(fmt.fmt(1.5).unwrap(), "1.5");
+        assert_eq!(fmt.fmt(2.0).unwrap(), "2");
+        assert_eq!(fmt.fmt(2.0001).unwrap(), "2");
+        assert_eq!(fmt.fmt(-0.25).unwrap(), "-0.25");
+    }
+
+    #[test]
+    fn min_and_max() {
+        let fmt = NumFmt::builder()
+            .min_fraction_digits(Some(2))
+            .max_fraction_digits(Some(4))
+            .build();
+        assert_eq!(fmt.fmt(7).unwrap(), "7.00");
+        assert_eq!(fmt.fmt(7.5).unwrap(), "7.50");
+        assert_eq!(fmt.fmt(7.125).unwrap(), "7.125");
+        assert_eq!(fmt.fmt(7.123456).unwrap(), "7.1234");
+    }
+
+    #[test]
+    fn min_exceeding_max() {
+        let fmt = NumFmt::builder()
+            .min_fraction_digits(Some(3))
+            .max_fraction_digits(Some(1))
+            .build();
+        assert_eq!(fmt.fmt(7.25).unwrap(), "7.250");
+    }
+
+    #[test]
+    fn dynamic_precision_overrides() {
+        let fmt = NumFmt::builder()
+            .min_fraction_digits(Some(0))
+            .max_fraction_digits(Some(4))
+            .build();
+        assert_eq!(fmt.fmt_with(7.5, Dynamic::precision(3)).unwrap(), "7.500");
+    }
+
+    #[test]
+    fn force_decimal_point() {
+        let fmt = NumFmt::builder()
+            .force_decimal_point(true)
+            .precision(Some(0))
+            .build();
+        assert_eq!(fmt.fmt(2.75).unwrap(), "2.");
+        assert_eq!(fmt.fmt(-3).unwrap(), "-3.");
+    }
+
+    #[test]
+    fn force_decimal_point_with_trimming() {
+        let fmt = NumFmt::builder()
+            .force_decimal_point(true)
+            .precision(Some(2))
+            .min_fraction_digits(Some(0))
+            .build();
+        assert_eq!(fmt.fmt(2.0).unwrap(), "2.");
+        assert_eq!(fmt.fmt(2.5).unwrap(), "2.5");
+    }
+
+    #[test]
+    fn decimal_alignment() {
+        let fmt = NumFmt::builder()
+            .align(Align::Decimal)
+            .width(3)
+            .precision(Some(2))
+            .min_fraction_digits(Some(0))
+            .build();
+        assert_eq!(fmt.fmt(1.5).unwrap(), "  1.5");
+        assert_eq!(fmt.fmt(12.0).unwrap(), " 12");
+    }
+}
+
+mod scientific {
+    use num_runtime_fmt::{Base, Builder, Error, ExponentStyle, Notation, NumFmt, Overflow, Ratio};
+
+    fn scientific() -> Builder {
+        NumFmt::builder().notation(Notation::Scientific)
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            NumFmt::from_str("e").unwrap().fmt(1234.5).unwrap(),
+            "1.2345e3"
+        );
+        assert_eq!(
+            NumFmt::from_str("E").unwrap().fmt(0.00125).unwrap(),
+            "1.25E-3"
+        );
+        assert_eq!(
+            NumFmt::from_str(".2e").unwrap().fmt(1234.5).unwrap(),
+            "1.23e3"
+        );
+        assert_eq!(
+            NumFmt::from_str("+10.1e").unwrap().fmt(1234.5).unwrap(),
+            "    +1.2e3"
+        );
+    }
+
+    #[test]
+    fn zero() {
+        assert_eq!(scientific().build().fmt(0).unwrap(), "0e0");
+        assert_eq!(
+            scientific().precision(Some(1)).build().fmt(0.0).unwrap(),
+            "0.0e0"
+        );
+    }
+
+    #[test]
+    fn integers() {
+        let fmt = scientific().build();
+        assert_eq!(fmt.fmt(7).unwrap(), "7e0");
+        assert_eq!(fmt.fmt(1_000_000_u32).unwrap(), "1e6");
+        assert_eq!(fmt.fmt(-120).unwrap(), "-1.2e2");
+    }
+
+    #[test]
+    fn zero_handler() {
+        let fmt = scientific().zero(true).width(8).build();
+        assert_eq!(fmt.fmt(-1.5).unwrap(), "-001.5e0");
+    }
+
+    #[test]
+    fn with_ratio() {
+        let fmt = scientific().ratio(Some(Ratio::Percent)).build();
+        assert_eq!(fmt.fmt(12.5).unwrap(), "1.25e3%");
+    }
+
+    #[test]
+    fn incompatible_base() {
+        let fmt = scientific().base(Base::LowerHex).build();
+        assert_eq!(fmt.fmt(12).unwrap_err(), Error::IncompatibleNotation);
+    }
+
+    #[test]
+    fn styles() {
+        for (style, expect) in [
+            (ExponentStyle::LowerE, "1.23e-4"),
+            (ExponentStyle::UpperE, "1.23E-4"),
+            (ExponentStyle::Superscript, "1.23×10⁻⁴"),
+            (ExponentStyle::Caret, "1.23×10^-4"),
+            (ExponentStyle::DotCaret, "1.23·10^-4"),
+        ] {
+            let fmt = scientific().exponent_style(style).build();
+            assert_eq!(fmt.fmt(0.000123).unwrap(), expect);
+        }
+    }
+
+    #[test]
+    fn superscript_positive() {
+        let fmt = scientific()
+            .exponent_style(ExponentStyle::Superscript)
+            .build();
+        assert_eq!(fmt.fmt(6.02e23).unwrap(), "6.02×10²³");
+    }
+
+    #[test]
+    fn exponent_counts_toward_width() {
+        let fmt = scientific()
+            .exponent_style(ExponentStyle::Superscript)
+            .width(10)
+            .build();
+        assert_eq!(fmt.fmt(0.000123).unwrap(), " 1.23×10⁻⁴");
+    }
+
+    #[test]
+    fn overflow_uses_style() {
+        let fmt = NumFmt::builder()
+            .max_width(Some(8))
+            .overflow(Overflow::Scientific)
+            .exponent_style(ExponentStyle::Caret)
+            .build();
+        assert_eq!(fmt.fmt(123456789).unwrap(), "1.2×10^8");
+    }
+}
+
+mod parts {
+    use num_runtime_fmt::{
+        Align, Currency, ExponentStyle, Notation, NumFmt, Overflow, Part, PartKind, Sign,
+    };
+    use PartKind::*;
+
+    fn parts(fmt: &NumFmt, number: f64) -> Vec<(PartKind, String)> {
+        fmt.fmt_to_parts(number)
+            .unwrap()
+            .into_iter()
+            .map(|Part { kind, text }| (kind, text))
+            .collect()
+    }
+
+    fn expect(parts: &[(PartKind, &str)]) -> Vec<(PartKind, String)> {
+        parts
+            .iter()
+            .map(|&(kind, text)| (kind, text.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn concatenation_matches_fmt() {
+        for spec in ["", "*^12.2,", "+#010", "(12.1,", "=+10", "08.3"] {
+            let fmt = NumFmt::from_str(spec).unwrap();
+            for number in [0.0, -1.5, 1234567.25] {
+                let concatenated: String = fmt
+                    .fmt_to_parts(number)
+                    .unwrap()
+                    .into_iter()
+                    .map(|part| part.text)
+                    .collect();
+                assert_eq!(concatenated, fmt.fmt(number).unwrap(), "{:?}", spec);
+            }
+        }
+    }
+
+    #[test]
+    fn padding_and_sign() {
+        let fmt = NumFmt::from_str("*>8").unwrap();
+        assert_eq!(
+            parts(&fmt, -12.5),
+            expect(&[
+                (Padding, "***"),
+                (Sign, "-"),
+                (Integer, "12"),
+                (DecimalSeparator, "."),
+                (Fraction, "5"),
+            ])
+        );
+    }
+
+    #[test]
+    fn zero_padding_is_integer() {
+        let fmt = NumFmt::from_str("06,").unwrap();
+        assert_eq!(
+            parts(&fmt, 12.0),
+            expect(&[(Integer, "00"), (GroupSeparator, ","), (Integer, "012")])
+        );
+    }
+
+    #[test]
+    fn accounting() {
+        let fmt = NumFmt::builder()
+            .sign(Sign::Parentheses)
+            .currency(Currency::from_code("EUR"))
+            .align(Align::Left)
+            .width(12)
+            .build();
+        assert_eq!(
+            parts(&fmt, -2.5),
+            expect(&[
+                (Sign, "("),
+                (Integer, "2"),
+                (DecimalSeparator, "."),
+                (Fraction, "50"),
+                (Currency, " €"),
+                (Sign, ")"),
+                (Padding, "    "),
+            ])
+        );
+    }
+
+    #[test]
+    fn affixes_and_exponent() {
+        let fmt = NumFmt::builder()
+            .notation(Notation::Scientific)
+            .exponent_style(ExponentStyle::Superscript)
+            .prefix("≈")
+            .suffix(" m")
+            .build();
+        assert_eq!(
+            parts(&fmt, 0.0015),
+            expect(&[
+                (Prefix, "≈"),
+                (Integer, "1"),
+                (DecimalSeparator, "."),
+                (Fraction, "5"),
+                (Exponent, "×10⁻³"),
+                (Suffix, " m"),
+            ])
+        );
+    }
+
+    #[test]
+    fn hex() {
+        let fmt = NumFmt::from_str("#x_2").unwrap();
+        assert_eq!(
+            fmt.fmt_to_parts(0xabc).unwrap(),
+            vec![
+                Part::new(Prefix, "0x"),
+                Part::new(Integer, "a"),
+                Part::new(GroupSeparator, "_"),
+                Part::new(Integer, "bc"),
+            ]
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let fill = NumFmt::builder()
+            .max_width(Some(3))
+            .overflow(Overflow::Fill)
+            .build();
+        assert_eq!(
+            parts(&fill, 12345.0),
+            expect(&[(PartKind::Overflow, "###")])
+        );
+
+        let truncate = NumFmt::builder()
+            .max_width(Some(5))
+            .overflow(Overflow::Truncate)
+            .build();
+        assert_eq!(
+            parts(&truncate, 1.23456),
+            expect(&[
+                (Integer, "1"),
+                (DecimalSeparator, "."),
+                (Fraction, "23"),
+                (PartKind::Overflow, "…"),
+            ])
+        );
+    }
+}
+
+mod ansi {
+    use num_runtime_fmt::{AnsiStyle, NumFmt, PartKind};
+
+    #[test]
+    fn unstyled_by_default() {
+        let fmt = NumFmt::from_str("*^9.2,").unwrap();
+        assert_eq!(
+            fmt.fmt_ansi(-1234.5, &AnsiStyle::new()).unwrap(),
+            fmt.fmt(-1234.5).unwrap()
+        );
+    }
+
+    #[test]
+    fn width_counts_visible_chars() {
+        let style = AnsiStyle::new().part(PartKind::Integer, "1");
+        let fmt = NumFmt::from_str("-<5").unwrap();
+        assert_eq!(fmt.fmt_ansi(12, &style).unwrap(), "\x1b[1m12\x1b[0m---");
+    }
+
+    #[test]
+    fn negative_only_for_negatives() {
+        let style = AnsiStyle::new().negative("31");
+        let fmt = NumFmt::from_str("+").unwrap();
+        assert_eq!(fmt.fmt_ansi(1, &style).unwrap(), "+1");
+        assert_eq!(
+            fmt.fmt_ansi(-1, &style).unwrap(),
+            "\x1b[31m-\x1b[0m\x1b[31m1\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn negative_does_not_style_padding_or_affixes() {
+        let style = AnsiStyle::new().negative("31");
+        let fmt = NumFmt::builder().suffix(" m").width(5).build();
+        assert_eq!(
+            fmt.fmt_ansi(-2, &style).unwrap(),
+            " \x1b[31m-\x1b[0m\x1b[31m2\x1b[0m m"
+        );
+    }
+
+    #[test]
+    fn table_palette() {
+        let fmt = NumFmt::builder().suffix("%").width(5).build();
+        assert_eq!(
+            fmt.fmt_ansi(-2, &AnsiStyle::table()).unwrap(),
+            "\x1b[2m  \x1b[0m\x1b[31m-\x1b[0m\x1b[31m2\x1b[0m\x1b[36m%\x1b[0m"
+        );
+    }
+}
+
+mod markup {
+    use num_runtime_fmt::{Currency, ExponentStyle, Markup, Notation, NumFmt, Sign};
+
+    const HTML: Markup = Markup::Html { classes: false };
+
+    #[test]
+    fn html_escapes() {
+        let fmt = NumFmt::builder().prefix("<").suffix(" & co").build();
+        assert_eq!(fmt.fmt_markup(5, HTML).unwrap(), "&lt;5 &amp; co");
+    }
+
+    #[test]
+    fn html_classes() {
+        let fmt = NumFmt::from_str("-,").unwrap();
+        assert_eq!(
+            fmt.fmt_markup(-1234, Markup::Html { classes: true })
+                .unwrap(),
+            concat!(
+                r#"<span class="num-sign">-</span>"#,
+                r#"<span class="num-integer">1</span>"#,
+                r#"<span class="num-group-separator">,</span>"#,
+                r#"<span class="num-integer">234</span>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn html_exponent_any_style() {
+        for style in [
+            ExponentStyle::LowerE,
+            ExponentStyle::Superscript,
+            ExponentStyle::Caret,
+        ] {
+            let fmt = NumFmt::builder()
+                .notation(Notation::Scientific)
+                .exponent_style(style)
+                .build();
+            assert_eq!(fmt.fmt_markup(25000, HTML).unwrap(), "2.5×10<sup>4</sup>");
+        }
+        let fmt = NumFmt::builder()
+            .notation(Notation::Scientific)
+            .exponent_style(ExponentStyle::DotCaret)
+            .build();
+        assert_eq!(fmt.fmt_markup(25000, HTML).unwrap(), "2.5·10<sup>4</sup>");
+    }
+
+    #[test]
+    fn padding_matches_fmt() {
+        let fmt = NumFmt::from_str("12.2e").unwrap();
+        assert_eq!(fmt.fmt(1.5).unwrap(), "      1.50e0");
+        assert_eq!(
+            fmt.fmt_markup(1.5, HTML).unwrap(),
+            "      1.50×10<sup>0</sup>"
+        );
+        assert_eq!(
+            fmt.fmt_markup(-1.5e-12, Markup::Latex).unwrap(),
+            r"\ \ \ -1.50\times 10^{-12}"
+        );
+        assert_eq!(
+            fmt.fmt_markup(1.5, Markup::Markdown).unwrap(),
+            "      1.50e0"
+        );
+
+        let fmt = NumFmt::builder()
+            .notation(Notation::Scientific)
+            .exponent_style(ExponentStyle::Superscript)
+            .precision(Some(1))
+            .width(11)
+            .build();
+        assert_eq!(fmt.fmt(-2.5e-11).unwrap(), " -2.5×10⁻¹¹");
+        assert_eq!(
+            fmt.fmt_markup(-2.5e-11, HTML).unwrap(),
+            " -2.5×10<sup>-11</sup>"
+        );
+    }
+
+    #[test]
+    fn latex() {
+        let fmt = NumFmt::from_str(".1%,").unwrap();
+        assert_eq!(
+            fmt.fmt_markup(12.3456, Markup::Latex).unwrap(),
+            r"1\,234.5\%"
+        );
+    }
+
+    #[test]
+    fn latex_decimal_comma() {
+        let fmt = NumFmt::builder()
+            .separator(Some('.'))
+            .decimal_separator(',')
+            .build();
+        assert_eq!(
+            fmt.fmt_markup(1234.5, Markup::Latex).unwrap(),
+            r"1\,234{,}5"
+        );
+    }
+
+    #[test]
+    fn latex_exponent() {
+        let fmt = NumFmt::from_str("e").unwrap();
+        assert_eq!(
+            fmt.fmt_markup(-0.0015, Markup::Latex).unwrap(),
+            r"-1.5\times 10^{-3}"
+        );
+        let fmt = NumFmt::builder()
+            .notation(Notation::Scientific)
+            .exponent_style(ExponentStyle::DotCaret)
+            .build();
+        assert_eq!(
+            fmt.fmt_markup(150, Markup::Latex).unwrap(),
+            r"1.5\cdot 10^{2}"
+        );
+    }
+
+    #[test]
+    fn latex_spaces_and_currency() {
+        let fmt = NumFmt::builder()
+            .currency(Currency::from_code("USD"))
+            .sign(Sign::CreditDebit)
+            .width(8)
+            .build();
+        assert_eq!(fmt.fmt_markup(-2, Markup::Latex).unwrap(), r"\$2.00\ CR");
+    }
+
+    #[test]
+    fn markdown() {
+        let fmt = NumFmt::builder()
+            .sign(Sign::Parentheses)
+            .suffix(" *")
+            .base(num_runtime_fmt::Base::Decimal)
+            .build();
+        assert_eq!(fmt.fmt_markup(-3, Markup::Markdown).unwrap(), r"\(3\) \*");
+        let fmt = NumFmt::from_str("_").unwrap();
+        assert_eq!(fmt.fmt_markup(1000, Markup::Markdown).unwrap(), r"1\_000");
+    }
+}
+
+mod write {
+    use num_runtime_fmt::{Error, NumFmt, Overflow};
+    use std::fmt;
+    #[cfg(feature = "std")]
+    use std::io;
+
+    /// Writer which fails once it has accepted `capacity` bytes.
+    struct Limited {
+        written: String,
+        capacity: usize,
+    }
+
+    impl fmt::Write for Limited {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            if self.written.len() + s.len() > self.capacity {
+                return Err(fmt::Error);
+            }
+            self.written.push_str(s);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn matches_fmt() {
+        for spec in ["", "*^12.2,", "+#010", "(12.1,", "=+10", "08.3e"] {
+            let fmt = NumFmt::from_str(spec).unwrap();
+            for number in [0.0, -1.5, 1234567.25] {
+                let mut written = String::new();
+                fmt.write_to(&mut written, number).unwrap();
+                assert_eq!(written, fmt.fmt(number).unwrap(), "{:?}", spec);
+
+                #[cfg(feature = "std")]
+                {
+                    let mut bytes = Vec::new();
+                    fmt.write_io(&mut bytes, number).unwrap();
+                    assert_eq!(bytes, written.as_bytes(), "{:?}", spec);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn appends() {
+        let fmt = NumFmt::from_str("03").unwrap();
+        let mut buffer = String::from("id=");
+        fmt.write_to(&mut buffer, 7).unwrap();
+        assert_eq!(buffer, "id=007");
+    }
+
+    #[test]
+    fn overflow() {
+        let fmt = NumFmt::builder()
+            .max_width(Some(4))
+            .overflow(Overflow::Truncate)
+            .build();
+        let mut buffer = String::new();
+        fmt.write_to(&mut buffer, 123456).unwrap();
+        assert_eq!(buffer, "123…");
+
+        let fmt = NumFmt::builder()
+            .max_width(Some(4))
+            .overflow(Overflow::Error)
+            .build();
+        let mut buffer = String::new();
+        assert_eq!(
+            fmt.write_to(&mut buffer, 123456).unwrap_err(),
+            Error::Overflow(6, 4)
+        );
+        // nothing is written when the number overflows
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn writer_error() {
+        let fmt = NumFmt::from_str(">8").unwrap();
+        let mut writer = Limited {
+            written: String::new(),
+            capacity: 3,
+        };
+        assert_eq!(fmt.write_to(&mut writer, 1).unwrap_err(), Error::Write);
+        assert_eq!(writer.written, "   ");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io_error() {
+        let fmt = NumFmt::from_str(">8").unwrap();
+        let mut buffer = [0_u8; 3];
+        let err = fmt.write_io(&mut &mut buffer[..], 1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io_format_error() {
+        use num_runtime_fmt::Base;
+
+        let fmt = NumFmt::builder().base(Base::Binary).build();
+        let err = fmt.write_io(&mut Vec::new(), 1.5).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let inner = err.into_inner().unwrap().downcast::<Error>().unwrap();
+        assert!(matches!(*inner, Error::NotImplemented(Base::Binary, _)));
+    }
+}
+
+mod display {
+    use num_runtime_fmt::{Dynamic, NumFmt, Overflow};
+    use std::fmt::Write;
+
+    #[test]
+    fn matches_fmt() {
+        for spec in ["", "*^12.2,", "+#010", "(12.1,", "=+10", "08.3e"] {
+            let fmt = NumFmt::from_str(spec).unwrap();
+            for number in [0.0, -1.5, 1234567.25] {
+                assert_eq!(
+                    fmt.display(number).to_string(),
+                    fmt.fmt(number).unwrap(),
+                    "{:?}",
+                    spec
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn dynamic() {
+        let fmt = NumFmt::from_str("-^").unwrap();
+        assert_eq!(
+            format!("|{}|", fmt.display_with(1, Dynamic::width(5))),
+            "|--1--|"
+        );
+    }
+
+    #[test]
+    fn write() {
+        let fmt = NumFmt::from_str("02").unwrap();
+        let mut out = String::new();
+        write!(out, "{}:{}", fmt.display(9), fmt.display(5)).unwrap();
+        assert_eq!(out, "09:05");
+    }
+
+    #[test]
+    fn error_fills_width() {
+        let fmt = NumFmt::from_str("6x").unwrap();
+        assert_eq!(fmt.display(0.5).to_string(), "######");
+        let fmt = NumFmt::from_str("x").unwrap();
+        assert_eq!(fmt.display(0.5).to_string(), "#");
+    }
+
+    #[test]
+    fn error_fallback() {
+        let fmt = NumFmt::from_str("x").unwrap();
+        assert_eq!(format!("<{}>", fmt.display(0.5).fallback("?")), "<?>");
+    }
+
+    #[test]
+    fn error_strict() {
+        let fmt = NumFmt::from_str("x").unwrap();
+        let mut out = String::new();
+        assert!(write!(out, "{}", fmt.display(0.5).strict()).is_err());
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn overflow_error() {
+        let fmt = NumFmt::builder()
+            .max_width(Some(3))
+            .overflow(Overflow::Error)
+            .build();
+        assert_eq!(fmt.display(1234).to_string(), "###");
+    }
+}
+
+mod std_flags {
+    use num_runtime_fmt::{NumFmt, StdFlags};
+
+    #[test]
+    fn default_settings() {
+        assert_eq!(format!("{}", StdFlags::new(1234.5)), "1234.5");
+        assert_eq!(format!("{:+}", StdFlags::new(5)), "+5");
+        assert_eq!(format!("{:*^7}", StdFlags::new(-12)), "**-12**");
+        assert_eq!(format!("{:.3}", StdFlags::new(0.25)), "0.250");
+    }
+
+    #[test]
+    fn overrides_fmt() {
+        let fmt = NumFmt::from_str("<10,").unwrap();
+        assert_eq!(format!("{}", fmt.std_flags(1234)), "1,234     ");
+        assert_eq!(format!("{:>12.2}", fmt.std_flags(1234.5)), "    1,234.50");
+        assert_eq!(format!("{:-^9}", fmt.std_flags(1234)), "--1,234--");
+    }
+
+    #[test]
+    fn bases() {
+        assert_eq!(format!("{:x}", StdFlags::new(255)), "ff");
+        assert_eq!(format!("{:#X}", StdFlags::new(255)), "0xFF");
+        assert_eq!(format!("{:b}", StdFlags::new(5)), "101");
+        assert_eq!(format!("{:#o}", StdFlags::new(8)), "0o10");
+        let fmt = NumFmt::builder().separator(Some('_')).build();
+        assert_eq!(format!("{:#x}", fmt.std_flags(0xabcdef)), "0xabc_def");
+    }
+
+    #[test]
+    fn zero_padding() {
+        assert_eq!(format!("{:08.2}", StdFlags::new(-1.5)), "-0001.50");
+        assert_eq!(format!("{:<05}", StdFlags::new(7)), "00007");
+        assert_eq!(format!("{:#010x}", StdFlags::new(0xabcd)), "0x0000abcd");
+    }
+
+    #[test]
+    fn alignment_overrides_zero_fmt() {
+        let fmt = NumFmt::from_str("08").unwrap();
+        assert_eq!(format!("{}", fmt.std_flags(-5)), "-0000005");
+        assert_eq!(format!("{:>10}", fmt.std_flags(-5)), "        -5");
+        assert_eq!(format!("{:*^10}", fmt.std_flags(-5)), "****-5****");
+        assert_eq!(format!("{:<010}", fmt.std_flags(-5)), "-000000005");
+    }
+
+    #[test]
+    fn matches_std() {
+        for number in [0, 7, -42, 123456] {
+            assert_eq!(
+                format!("{:>8}", StdFlags::new(number)),
+                format!("{:>8}", number)
+            );
+            assert_eq!(
+                format!("{:+06}", StdFlags::new(number)),
+                format!("{:+06}", number)
+            );
+            assert_eq!(
+                format!("{:<7}", StdFlags::new(number)),
+                format!("{:<7}", number)
+            );
+        }
+    }
+
+    #[test]
+    fn error_fills_width() {
+        assert_eq!(format!("{:4x}", StdFlags::new(0.5)), "####");
+    }
+}
+
+mod plan {
+    use num_runtime_fmt::{Dynamic, Error, NumFmt, Overflow};
+
+    #[test]
+    fn matches_fmt_with() {
+        let specs = [
+            "", "*^12.2,", "+#010", "(12.1,", "=+10", "08.3e", "v8.2", ">9%", "-<8.1_2",
+        ];
+        let dynamics = [
+            Dynamic::default(),
+            Dynamic::new(12, 3, None),
+            Dynamic::spacing(2),
+        ];
+        for spec in specs {
+            let fmt = NumFmt::from_str(spec).unwrap();
+            for dynamic in dynamics {
+                let plan = fmt.compile::<f64>(dynamic).unwrap();
+                for number in [0.0, -0.0, -1.5, 0.000123, 1234567.25] {
+                    assert_eq!(
+                        plan.fmt(number),
+                        fmt.fmt_with(number, dynamic),
+                        "{:?} {:?}",
+                        spec,
+                        dynamic
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn integer_bases() {
+        let fmt = NumFmt::from_str("#08x_4").unwrap();
+        let plan = fmt.compile::<u32>(Dynamic::default()).unwrap();
+        assert_eq!(plan.fmt(0xbeef).unwrap(), "0x0_beef");
+        assert_eq!(plan.fmt(0xdead_beef).unwrap(), "0xdead_beef");
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        let fmt = NumFmt::builder()
+            .zero(true)
+            .align(num_runtime_fmt::Align::Left)
+            .build();
+        assert_eq!(
+            fmt.compile::<i32>(Dynamic::default()).unwrap_err(),
+            Error::IncompatibleAlignment
+        );
+    }
+
+    #[test]
+    fn unsupported_number() {
+        let fmt = NumFmt::from_str("x").unwrap();
+        let plan = fmt.compile::<f32>(Dynamic::default()).unwrap();
+        assert!(matches!(plan.fmt(1.5), Err(Error::NotImplemented(..))));
+    }
+
+    #[test]
+    fn overflow() {
+        let fmt = NumFmt::builder()
+            .max_width(Some(6))
+            .overflow(Overflow::Scientific)
+            .build();
+        let plan = fmt.compile::<i64>(Dynamic::default()).unwrap();
+        assert_eq!(plan.fmt(123).unwrap(), "123");
+        assert_eq!(plan.fmt(123456789).unwrap(), "1.23e8");
+    }
+
+    #[test]
+    fn write() {
+        let fmt = NumFmt::from_str("03").unwrap();
+        let plan = fmt.compile::<u8>(Dynamic::default()).unwrap();
+        let mut text = String::new();
+        for number in [1, 22] {
+            plan.write_to(&mut text, number).unwrap();
+        }
+        assert_eq!(text, "001022");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_io() {
+        let fmt = NumFmt::from_str("03").unwrap();
+        let plan = fmt.compile::<u8>(Dynamic::default()).unwrap();
+        let mut bytes = Vec::new();
+        for number in [1, 22] {
+            plan.write_io(&mut bytes, number).unwrap();
+        }
+        assert_eq!(bytes, b"001022");
+    }
+}
+
+mod buffers {
+    use super::*;
+
+    #[test]
+    fn wide_zero_padding() {
+        let fmt = NumFmt::builder()
+            .zero(true)
+            .width(300)
+            .separator(Some('_'))
+            .build();
+        let rendered = fmt.fmt(-12.5).unwrap();
+        assert_eq!(rendered.len(), 300);
+        assert!(rendered.starts_with("-0_000"));
+        assert!(rendered.ends_with("0_012.5"));
+    }
+
+    #[test]
+    fn zero_runs() {
+        let fmt = NumFmt::from_str(".300,").unwrap();
+        let rendered = fmt.fmt(1e300).unwrap();
+        assert_eq!(rendered.len(), 301 + 100 + 1 + 300);
+        assert!(rendered.starts_with("1,000,"));
+        assert!(rendered.contains(",000.000"));
+
+        let rendered = NumFmt::default().fmt(5e-324).unwrap();
+        assert_eq!(rendered.len(), "0.".len() + 324);
+        assert!(rendered.ends_with("0005"));
+
+        let fmt = NumFmt::from_str("0200,3").unwrap();
+        let rendered = fmt.fmt(1_000_001).unwrap();
+        assert_eq!(rendered.len(), 200);
+        assert!(rendered.ends_with("0,001,000,001"));
+    }
+
+    /// A number with the given decimal digits, most significant first.
+    struct Digits(&'static str);
+
+    impl num_runtime_fmt::Numeric for Digits {
+        type BinIter = std::iter::Empty<char>;
+        type OctIter = std::iter::Empty<char>;
+        type DecLeftIter = std::iter::Rev<std::str::Chars<'static>>;
+        type DecRightIter = std::iter::Empty<char>;
+        type HexIter = std::iter::Empty<char>;
+
+        fn binary(&self) -> Option<Self::BinIter> {
+            None
+        }
+
+        fn octal(&self) -> Option<Self::OctIter> {
+            None
+        }
+
+        fn decimal(&self) -> (Self::DecLeftIter, Option<Self::DecRightIter>) {
+            (self.0.chars().rev(), None)
+        }
+
+        fn hex(&self) -> Option<Self::HexIter> {
+            None
+        }
+
+        fn is_negative(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn spill_to_heap() {
+        let digits = "1234567890".repeat(30);
+        let digits: &'static str = Box::leak(digits.into_boxed_str());
+        assert_eq!(NumFmt::default().fmt(Digits(digits)).unwrap(), digits);
+
+        let fmt = NumFmt::from_str("_5").unwrap();
+        let rendered = fmt.fmt(Digits(digits)).unwrap();
+        assert_eq!(rendered.replace('_', ""), digits);
+        assert!(rendered.starts_with("12345_67890_"));
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(
+            Error::Overflow(5, 3).to_string(),
+            "rendered width 5 exceeds the maximum width 3",
+        );
+        assert_eq!(
+            Error::IncompatibleScale.to_string(),
+            "Scaling is only compatible with Base::Decimal",
+        );
+    }
+}
+
+mod parse_errors {
+    use num_runtime_fmt::parse::{Component, ErrorKind};
+
+    #[test]
+    fn from_str_owns_input() {
+        let err = {
+            let input = String::from("<,x");
+            input.parse::<num_runtime_fmt::NumFmt>().unwrap_err()
+        };
+        assert_eq!(err.input(), "<,x");
+        assert_eq!(err.kind(), ErrorKind::Misplaced(Component::Format));
+        assert_eq!(
+            err.to_string(),
+            "the format type must come earlier in the format string\n  <,x\n    ^\nhelp: did you mean `<x,`?",
+        );
+    }
+}
+
+mod round_trip {
+    use num_runtime_fmt::{
+        Align, Base, ExponentStyle, Notation, NumFmt, Ratio, Sign, Unrepresentable, ZeroSign,
+    };
+    use proptest::prelude::*;
+
+    fn align() -> impl Strategy<Value = Align> {
+        prop_oneof![
+            Just(Align::Left),
+            Just(Align::Center),
+            Just(Align::Right),
+            Just(Align::Decimal),
+            Just(Align::AfterSign),
+        ]
+    }
+
+    fn sign() -> impl Strategy<Value = Sign> {
+        prop_oneof![
+            Just(Sign::OnlyMinus),
+            Just(Sign::PlusAndMinus),
+            Just(Sign::SpaceAndMinus),
+            Just(Sign::Parentheses),
+            Just(Sign::TrailingMinus),
+            Just(Sign::CreditDebit),
+        ]
+    }
+
+    fn zero_sign() -> impl Strategy<Value = ZeroSign> {
+        prop_oneof![
+            Just(ZeroSign::Natural),
+            Just(ZeroSign::Positive),
+            Just(ZeroSign::PlusMinus),
+            Just(ZeroSign::Preserve),
+            Just(ZeroSign::Unsigned),
+        ]
+    }
+
+    fn format() -> impl Strategy<Value = (Base, Notation, ExponentStyle, Option<Ratio>)> {
+        let base = prop_oneof![
+            Just(Base::Binary),
+            Just(Base::Octal),
+            Just(Base::Decimal),
+            Just(Base::LowerHex),
+            Just(Base::UpperHex),
+        ];
+        let notation = prop_oneof![Just(Notation::Positional), Just(Notation::Scientific)];
+        let exponent_style = prop_oneof![
+            Just(ExponentStyle::LowerE),
+            Just(ExponentStyle::UpperE),
+            Just(ExponentStyle::Caret),
+        ];
+        let ratio = proptest::option::of(prop_oneof![
+            Just(Ratio::Percent),
+            Just(Ratio::PerMille),
+            Just(Ratio::BasisPoint),
+        ]);
+        (base, notation, exponent_style, ratio)
+    }
+
+    prop_compose! {
+        fn num_fmt()(
+            fill in proptest::option::of(prop_oneof![
+                Just('0'),
+                Just(' '),
+                Just('<'),
+                Just('ö'),
+                any::<char>(),
+            ]),
+            align in align(),
+            sign in sign(),
+            zero_sign in zero_sign(),
+            hash in any::<bool>(),
+            zero in any::<bool>(),
+            width in prop_oneof![Just(0), 0..100usize, any::<usize>()],
+            precision in proptest::option::of(prop_oneof![0..20usize, any::<usize>()]),
+            (base, notation, exponent_style, ratio) in format(),
+            separator in proptest::option::of(prop_oneof![
+                Just('_'),
+                Just(','),
+                Just(' '),
+                Just('.'),
+            ]),
+            spacing in proptest::option::of(0..10usize),
+            decimal_separator in proptest::option::of(Just(',')),
+        ) -> NumFmt {
+            let mut builder = NumFmt::builder()
+                .zero(zero)
+                .align(align)
+                .sign(sign)
+                .zero_sign(zero_sign)
+                .hash(hash)
+                .width(width)
+                .precision(precision)
+                .base(base)
+                .notation(notation)
+                .exponent_style(exponent_style)
+                .ratio(ratio)
+                .separator(separator);
+            if let Some(fill) = fill {
+                builder = builder.fill(fill);
+            }
+            if let Some(spacing) = spacing {
+                builder = builder.spacing(spacing);
+            }
+            if let Some(decimal_separator) = decimal_separator {
+                builder = builder.decimal_separator(decimal_separator);
+            }
+            builder.build()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn displayed_settings_parse_back(fmt in num_fmt()) {
+            if let Ok(format_string) = fmt.format_string() {
+                let format_string = format_string.to_string();
+                let parsed = NumFmt::from_str(&format_string);
+                prop_assert_eq!(parsed.as_ref().ok(), Some(&fmt), "{:?}", format_string);
+            }
+        }
+
+        #[test]
+        fn parsed_strings_display_canonically(format_string in "[ <^>v=a*ö+(tc-]{0,3}[z±]?#?0?[0-9]{0,3}(\\.[0-9]{1,2})?(b|o|d|x|X|e|E|%|‰|bp)?([_, ][0-9]?)?") {
+            if let Ok(fmt) = NumFmt::from_str(&format_string) {
+                let canonical = fmt.format_string().expect("parsed settings are representable").to_string();
+                let reparsed = NumFmt::from_str(&canonical).expect("canonical strings parse");
+                prop_assert_eq!(&reparsed, &fmt, "{:?} displayed as {:?}", format_string, canonical);
+                prop_assert_eq!(reparsed.format_string().unwrap().to_string(), canonical);
+            }
+        }
+    }
+
+    #[test]
+    fn unrepresentable_settings() {
+        let fmt = NumFmt::builder().fill_pattern("-=").build();
+        assert_eq!(
+            fmt.format_string().unwrap_err(),
+            Unrepresentable::FillPattern
+        );
+
+        let fmt = NumFmt::builder().fill('\n').build();
+        assert_eq!(
+            fmt.format_string().unwrap_err(),
+            Unrepresentable::NewlineFill
+        );
+
+        let fmt = NumFmt::builder().decimal_separator(',').build();
+        assert_eq!(
+            fmt.format_string().unwrap_err(),
+            Unrepresentable::DecimalSeparator
+        );
+
+        let fmt = NumFmt::builder().separator(Some('\'')).build();
+        assert_eq!(
+            fmt.format_string().unwrap_err(),
+            Unrepresentable::Separator('\'')
+        );
+        assert_eq!(
+            fmt.format_string().unwrap_err().to_string(),
+            "the separator '\\'' can't be expressed in a format string",
+        );
+
+        let fmt = NumFmt::builder().sign(Sign::SpaceAndMinus).width(5).build();
+        assert_eq!(fmt.format_string().unwrap().to_string(), " 5d");
+    }
+
+    #[test]
+    fn unrepresentable_settings_do_not_panic() {
+        let fmt = NumFmt::builder()
+            .separator(Some('.'))
+            .decimal_separator(',')
+            .build();
+        assert_eq!(
+            fmt.format_string()
+                .map(|format_string| format_string.to_string()),
+            Err(Unrepresentable::Separator('.')),
+        );
+        // the settings still render numbers
+        assert_eq!(fmt.fmt(1234.5).unwrap(), "1.234,5");
+    }
+}
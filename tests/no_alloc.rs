@@ -0,0 +1,145 @@
+//! Rendering without allocating: everything available without the `alloc` feature.
+
+use num_runtime_fmt::{Dynamic, Error, NumFmt, Numeric};
+use std::iter::{repeat_n, Empty, RepeatN};
+
+/// Render into a `String`; the writer is the only allocation.
+fn write<N: Numeric>(fmt: &NumFmt, number: N) -> Result<String, Error> {
+    let mut rendered = String::new();
+    fmt.write_to(&mut rendered, number)?;
+    Ok(rendered)
+}
+
+mod write_to {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let fmt = NumFmt::from_str("*^12.2,").unwrap();
+        assert_eq!(write(&fmt, -1234.5).unwrap(), "**-1,234.50*");
+    }
+
+    #[test]
+    fn builder() {
+        let fmt = NumFmt::builder()
+            .fill('·')
+            .width(10)
+            .separator(Some('_'))
+            .spacing(4)
+            .build();
+        assert_eq!(write(&fmt, 0xdead_beef_u32).unwrap(), "37_3592_8559");
+    }
+
+    #[test]
+    fn with_dynamic() {
+        let fmt = NumFmt::from_str("05").unwrap();
+        let mut rendered = String::new();
+        fmt.write_to_with(&mut rendered, 42, Dynamic::width(7))
+            .unwrap();
+        assert_eq!(rendered, "0000042");
+    }
+
+    #[test]
+    fn wide_zero_padding() {
+        let fmt = NumFmt::from_str("0200,").unwrap();
+        let rendered = write(&fmt, -5).unwrap();
+        assert_eq!(rendered.len(), 200);
+        assert!(rendered.starts_with("-000,000"));
+        assert!(rendered.ends_with(",005"));
+    }
+
+    #[test]
+    fn extreme_floats() {
+        let rendered = write(&NumFmt::default(), 1e300).unwrap();
+        assert_eq!(rendered.len(), 301);
+        assert!(rendered.starts_with("10000"));
+
+        let rendered = write(&NumFmt::from_str(".400").unwrap(), f64::MIN_POSITIVE).unwrap();
+        assert_eq!(rendered.len(), "0.".len() + 400);
+        assert!(rendered.starts_with("0.000"));
+
+        assert_eq!(
+            write(&NumFmt::from_str("e").unwrap(), 1e-300).unwrap(),
+            "1e-300"
+        );
+        assert_eq!(write(&NumFmt::default(), f64::NAN).unwrap(), "NaN");
+    }
+}
+
+mod display {
+    use super::*;
+
+    #[test]
+    fn in_format_args() {
+        let fmt = NumFmt::from_str(">8.3").unwrap();
+        assert_eq!(format!("[{}]", fmt.display(1.23456)), "[   1.234]");
+        assert_eq!(
+            format!("[{}]", fmt.display_with(2, Dynamic::precision(1))),
+            "[     2.0]",
+        );
+    }
+
+    #[test]
+    fn on_error() {
+        let fmt = NumFmt::from_str(">4x").unwrap();
+        assert_eq!(format!("{}", fmt.display(1.5)), "####");
+        assert_eq!(format!("{}", fmt.display(1.5).fallback("n/a")), "n/a");
+    }
+}
+
+mod capacity {
+    use super::*;
+
+    /// A number whose decimal digits are `len` ones.
+    #[derive(Clone, Copy)]
+    struct Repunit(usize);
+
+    impl Numeric for Repunit {
+        type BinIter = Empty<char>;
+        type OctIter = Empty<char>;
+        type DecLeftIter = RepeatN<char>;
+        type DecRightIter = Empty<char>;
+        type HexIter = Empty<char>;
+
+        fn binary(&self) -> Option<Self::BinIter> {
+            None
+        }
+
+        fn octal(&self) -> Option<Self::OctIter> {
+            None
+        }
+
+        fn decimal(&self) -> (Self::DecLeftIter, Option<Self::DecRightIter>) {
+            (repeat_n('1', self.0), None)
+        }
+
+        fn hex(&self) -> Option<Self::HexIter> {
+            None
+        }
+
+        fn is_negative(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn digits_fit() {
+        let rendered = write(&NumFmt::from_str(",").unwrap(), Repunit(128)).unwrap();
+        assert_eq!(rendered.len(), 128 + 42);
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn digits_exceed_capacity() {
+        let fmt = NumFmt::from_str("8").unwrap();
+        assert_eq!(write(&fmt, Repunit(129)).unwrap_err(), Error::Capacity);
+        assert_eq!(format!("{}", fmt.display(Repunit(129))), "########");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn digits_grow() {
+        let rendered = write(&NumFmt::default(), Repunit(1000)).unwrap();
+        assert_eq!(rendered.len(), 1000);
+    }
+}